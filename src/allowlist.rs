@@ -0,0 +1,45 @@
+use cosmwasm_std::{ensure, Addr, Uint128};
+use sha2::{Digest, Sha256};
+
+use crate::msg::Allowlist;
+use crate::ContractError;
+
+/// Leaf of an allowlisted address, the sha256 hash of the address followed by its cap when it has one
+pub fn leaf(address: &Addr, cap: Option<Uint128>) -> [u8; 32] {
+    let leaf = match cap {
+        Some(cap) => format!("{address}{cap}"),
+        None => address.to_string(),
+    };
+    Sha256::digest(leaf.as_bytes()).into()
+}
+
+/// Decodes a hex encoded sha256 hash of the merkle tree
+pub fn decode_hash(hash: &str) -> Result<[u8; 32], ContractError> {
+    let mut buf = [0u8; 32];
+    hex::decode_to_slice(hash, &mut buf)
+        .map_err(|_| ContractError::InvalidMerkleHash(hash.to_string()))?;
+    Ok(buf)
+}
+
+/// Ensures the proof leads from the leaf of the address and cap to the merkle root of the allowlist
+/// Pairs are hashed in sorted order so the proof does not carry the position of the siblings
+pub fn verify(
+    allowlist: &Allowlist,
+    address: &Addr,
+    cap: Option<Uint128>,
+    proof: &[String],
+) -> Result<(), ContractError> {
+    let root = decode_hash(&allowlist.merkle_root)?;
+    let mut hash = leaf(address, cap);
+    for sibling in proof {
+        let mut pair = [hash, decode_hash(sibling)?];
+        pair.sort_unstable();
+        hash = Sha256::digest(pair.concat()).into();
+    }
+
+    ensure!(
+        hash == root,
+        ContractError::NotAllowlisted(address.to_string())
+    );
+    Ok(())
+}
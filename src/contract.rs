@@ -4,35 +4,52 @@ use std::str::FromStr;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coin, coins, ensure, from_json, instantiate2_address, to_json_binary, wasm_execute, BankMsg,
-    Binary, CodeInfoResponse, Coin, CosmosMsg, Decimal, Decimal256, Deps, DepsMut, Env,
-    MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    coin, ensure, from_json, to_json_binary, wasm_execute, Addr, Api, BankMsg, Binary, Coin,
+    CosmosMsg, Decimal, Decimal256, Deps, DepsMut, Env, MessageInfo, Order, QuerierWrapper, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, SubMsgResponse, Timestamp, Uint128, Uint256,
+    WasmMsg,
 };
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
-use cw_utils::{NativeBalance, PaymentError};
-use fuzion_flows::{FlowCreate, FlowSchedule, FlowType};
+use cw_utils::{one_coin, parse_execute_response_data, NativeBalance, PaymentError};
 use fuzion_utilities::{Asset, AssetList, DenomUnit, LogoURIs};
-use kujira::{DenomMsg, KujiraMsg, KujiraQuery, Precision};
+use kujira::{Denom, DenomMsg, KujiraMsg, KujiraQuery};
 use kujira_orca::BidPoolsResponse;
-use kujira_pilot::Status;
+use kujira_pilot::{CreateOrca, CreateSale, Status};
 
-use crate::launch::Launch;
+use crate::allowlist;
+use crate::launch::{next_status, Launch};
+use crate::migrations::migrate_launches;
 use crate::msg::{
-    Bow, CallbackType, CategoryTypes, Config, Fin, LaunchStatus, Pilot, ReplyInfo, ReplyTypes,
-    Token, Tokenomics,
+    AllowlistBid, AllowlistEligibilityResponse, BidderPosition, Bow, CallbackType, CategoryTypes,
+    Config, Fin, LaunchAction, LaunchHistoryEntry, LaunchStatus, LiquidityDestination,
+    LiquidityLeftover, LiquidityPricing, Market, MarketParams, Pilot, PredictedAddressesResponse,
+    ReplyInfo, ReplyTypes, SaleAnalytics, SaleBounds, SaleResult, SimulatePostLaunchResponse,
+    SlotAnalytics, Token, Tokenomics,
+};
+use crate::post_launch;
+use crate::pricing;
+use crate::raise;
+use crate::state::{
+    launch, ALLOWLIST_BIDS, BID_POOLS, CONFIG, LAUNCH_HISTORY, MIGRATION_CURSOR, PENDING_BIDS,
+    PILOT_SALES, REPLIES,
 };
-use crate::state::{launch, CONFIG, REPLY};
 use crate::{ContractError, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 const CONTRACT_NAME: &str = "fuzion-kujira-keiko";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[entry_point]
-pub fn migrate(deps: DepsMut<KujiraQuery>, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(mut deps: DepsMut<KujiraQuery>, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let contract = env.contract.address.clone();
+    let (migrated, pending) = migrate_launches(deps.branch(), &env, &contract, &config, msg.limit)?;
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    Ok(Response::default())
+    Ok(Response::default()
+        .add_attribute("migrated_launches", migrated.to_string())
+        .add_attribute("migration_pending", pending.to_string()))
 }
 
 #[entry_point]
@@ -43,6 +60,7 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response<KujiraMsg>, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    msg.fin.price_precision.validate()?;
 
     let config = Config {
         owner: msg.owner,
@@ -52,6 +70,7 @@ pub fn instantiate(
         flows: msg.flows,
         fin: msg.fin,
         bow: msg.bow,
+        launch: msg.launch,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -75,6 +94,7 @@ pub fn execute(
             flows,
             fin,
             bow,
+            launch,
         } => {
             ensure!(info.sender == config.owner, ContractError::Unauthorized {});
             if let Some(owner) = owner {
@@ -93,11 +113,15 @@ pub fn execute(
                 config.flows = flows;
             }
             if let Some(fin) = *fin {
+                fin.price_precision.validate()?;
                 config.fin = fin;
             }
             if let Some(bow) = bow {
                 config.bow = bow;
             }
+            if let Some(launch) = launch {
+                config.launch = launch;
+            }
             CONFIG.save(deps.storage, &config)?;
             Ok(Response::default())
         }
@@ -105,47 +129,55 @@ pub fn execute(
             terms_conditions_accepted,
         } => {
             // Sets up the launch and takes the deposit
+            // Funds sent on top of the deposit prefund the BOW creation fee and keeper rewards
             let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
                 config.pilot.pilot_contract.clone(),
                 &kujira_pilot::QueryMsg::Config {},
             )?;
 
-            ensure!(
-                info.funds.len() == 1 && info.funds[0] == pilot_config.deposit,
-                ContractError::Payment(PaymentError::MissingDenom(pilot_config.deposit.denom))
-            );
+            let mut prefunded = NativeBalance(info.funds.clone());
+            prefunded.normalize();
+            let mut prefunded = prefunded.sub(pilot_config.deposit.clone()).map_err(|_| {
+                ContractError::Payment(PaymentError::MissingDenom(
+                    pilot_config.deposit.denom.clone(),
+                ))
+            })?;
+            prefunded.normalize();
 
             ensure!(
                 terms_conditions_accepted,
                 ContractError::TermsConditionsAccepted {}
             );
 
-            let existing_sale: Vec<Launch> = from_json(
-                query(
-                    deps.as_ref(),
-                    env,
-                    QueryMsg::LaunchesByOwner {
-                        owner: info.sender.clone(),
-                        start_after: None,
-                        limit: None,
-                    },
-                )
-                .unwrap(),
-            )
-            .unwrap();
-
+            // the active launches of the owner are only counted once every launch is migrated
+            ensure!(
+                MIGRATION_CURSOR.may_load(deps.storage)?.is_none(),
+                ContractError::MigrationPending {}
+            );
+            let active_launches = Launch::active_count_by_owner(deps.storage, &info.sender)?;
             ensure!(
-                existing_sale.is_empty(),
-                ContractError::SaleAlreadyExistsOwner(info.sender.to_string())
+                active_launches < config.launch.max_active_per_owner,
+                ContractError::MaxActiveLaunches(
+                    info.sender.to_string(),
+                    config.launch.max_active_per_owner.to_string()
+                )
             );
 
-            let launch = Launch::new(
+            let mut launch = Launch::new(
                 deps.storage,
                 info.sender.clone(),
-                info.funds[0].clone(),
+                pilot_config.deposit,
                 terms_conditions_accepted,
             );
+            launch.prefunded = prefunded.into_vec();
             launch.save(deps.storage)?;
+            launch.record(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::Create,
+                LaunchStatus::Created,
+            )?;
             Ok(Response::default().add_attribute("action", "create"))
         }
         ExecuteMsg::Token {
@@ -162,10 +194,7 @@ pub fn execute(
             let mut launch = Launch::load(deps.storage, idx)?;
             launch.is_owner(&info.sender)?;
 
-            ensure!(
-                launch.status == LaunchStatus::Created,
-                ContractError::InvalidStatus {}
-            );
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::Token)?;
 
             if create {
                 ensure!(
@@ -254,6 +283,7 @@ pub fn execute(
             // requires at least one sale and one liquidity category so that pilot and Bow can be set up
             let mut launch = Launch::load(deps.storage, idx)?;
             launch.is_owner(&info.sender)?;
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::Tokenomics)?;
 
             let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
                 config.pilot.pilot_contract.clone(),
@@ -261,23 +291,38 @@ pub fn execute(
             )?;
 
             ensure!(
-                launch.token.is_some() && launch.pilot.is_none(),
+                launch.token.is_some() && launch.pilots.is_empty(),
                 ContractError::Unauthorized {}
             );
 
-            // ensure only one sale category
-            let sale_category = categories
+            // ensure at least one sale category, each one is sold in its own Pilot round and each of
+            // its recipients in its own Pilot sale of the round
+            let sale_categories = categories
                 .iter()
                 .filter(|f| f.category_type == CategoryTypes::Sale);
 
             ensure!(
-                sale_category.clone().count() == 1,
+                sale_categories.clone().count() >= 1,
                 ContractError::OneSaleCategory {}
             );
 
+            let mut sale_amount = Uint128::zero();
+            let mut sales = 0usize;
+            for sale_category in sale_categories {
+                ensure!(
+                    !sale_category.recipients.is_empty(),
+                    ContractError::OneSaleCategoryRecipient {}
+                );
+                for recipient in sale_category.recipients.iter() {
+                    sale_amount = sale_amount.add(recipient.amount);
+                    sales += 1;
+                }
+            }
+
+            // sales and rounds are indexed by a u8 in the reply ids and the sale allocations
             ensure!(
-                sale_category.clone().next().unwrap().recipients.len() == 1,
-                ContractError::OneSaleCategoryRecipient {}
+                sales <= u8::MAX as usize + 1,
+                ContractError::TooManySales(sales.to_string())
             );
 
             // ensure only one liquidity category
@@ -295,12 +340,9 @@ pub fn execute(
                 ContractError::OneSaleCategoryRecipient {}
             );
 
-            let max_liquidity = Decimal256::from_atomics(
-                sale_category.clone().next().unwrap().recipients[0].amount,
-                0,
-            )
-            .unwrap()
-            .mul(Decimal256::one() - Decimal256::from(pilot_config.sale_fee));
+            let max_liquidity = Decimal256::from_atomics(sale_amount, 0)
+                .unwrap()
+                .mul(Decimal256::one() - Decimal256::from(pilot_config.sale_fee));
 
             ensure!(
                 Uint256::from_uint128(
@@ -313,9 +355,7 @@ pub fn execute(
             ensure!(
                 liquidity_category.into_iter().next().unwrap().recipients[0]
                     .amount
-                    .ge(&sale_category.into_iter().next().unwrap().recipients[0]
-                        .amount
-                        .mul(config.tokenomics.minimum_liquidity_one_side)),
+                    .ge(&sale_amount.mul(config.tokenomics.minimum_liquidity_one_side)),
                 ContractError::LiquidityAmountBelowRequired(
                     config.tokenomics.minimum_liquidity_one_side.to_string()
                 )
@@ -361,6 +401,70 @@ pub fn execute(
                 }
             }
 
+            // ensure that the liquidity targets split the whole liquidity allocation
+            for category in categories.clone() {
+                if let Some(targets) = category.liquidity {
+                    ensure!(
+                        category.category_type == CategoryTypes::Liquidity,
+                        ContractError::LiquidityTargetsNotAllowed(category.label)
+                    );
+                    let mut shares = Decimal::zero();
+                    for target in targets.iter() {
+                        ensure!(
+                            !target.share.is_zero(),
+                            ContractError::InvalidLiquidityShares(target.share.to_string())
+                        );
+                        shares += target.share;
+                        if let LiquidityDestination::ExistingFin { fin, bow } = &target.destination
+                        {
+                            deps.api.addr_validate(fin.as_str())?;
+                            deps.api.addr_validate(bow.as_str())?;
+                        }
+                    }
+                    ensure!(
+                        shares == Decimal::one(),
+                        ContractError::InvalidLiquidityShares(shares.to_string())
+                    );
+                }
+            }
+
+            // ensure that the LP vesting tranches split all LP tokens within the configured bounds
+            let bounds = config.tokenomics.lp_vest_bounds.clone();
+            for category in categories.clone() {
+                if let Some(tranches) = category.lp_vesting {
+                    ensure!(
+                        category.category_type == CategoryTypes::Liquidity,
+                        ContractError::LpVestingNotAllowed(category.label)
+                    );
+                    let mut shares = Decimal::zero();
+                    for tranche in tranches.iter() {
+                        ensure!(
+                            !tranche.share.is_zero(),
+                            ContractError::InvalidLpVestingShares(tranche.share.to_string())
+                        );
+                        shares += tranche.share;
+                        if let Some(taker) = tranche.taker.clone() {
+                            deps.api.addr_validate(taker.as_str())?;
+                        }
+                        ensure!(
+                            tranche.cliff <= tranche.duration
+                                && tranche.cliff >= bounds.min_cliff
+                                && tranche.cliff <= bounds.max_cliff.unwrap_or(u64::MAX)
+                                && tranche.duration >= bounds.min_duration
+                                && tranche.duration <= bounds.max_duration.unwrap_or(u64::MAX),
+                            ContractError::LpVestingOutOfBounds(
+                                tranche.cliff.to_string(),
+                                tranche.duration.to_string()
+                            )
+                        );
+                    }
+                    ensure!(
+                        shares == Decimal::one(),
+                        ContractError::InvalidLpVestingShares(shares.to_string())
+                    );
+                }
+            }
+
             let tokenomics: Tokenomics = Tokenomics { categories };
             launch.tokenomics = Some(tokenomics);
             launch.save(deps.storage)?;
@@ -369,17 +473,28 @@ pub fn execute(
                 .add_attribute("action", "tokenomics")
                 .add_attribute("idx", idx))
         }
-        ExecuteMsg::PilotSchedule { idx, sale, orca } => {
-            // Schedule the pilot sale and set the pilot status to planned
+        ExecuteMsg::PilotSchedule {
+            idx,
+            round,
+            recipient,
+            sale,
+            orca,
+            existing_fin,
+            market,
+        } => {
+            // Schedule a pilot sale of a round and set the launch status to scheduled
             // Does not create the Pilot Sale but stores the information for the pilot contract
             let mut launch = Launch::load(deps.storage, idx)?;
             launch.is_owner(&info.sender)?;
+            launch.transition(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::PilotSchedule,
+            )?;
 
             ensure!(
-                launch.token.is_some()
-                    && launch.tokenomics.is_some()
-                    && (launch.pilot.is_none()
-                        || (launch.pilot.is_some() && launch.status == LaunchStatus::Planned)),
+                launch.token.is_some() && launch.tokenomics.is_some(),
                 ContractError::Unauthorized {}
             );
 
@@ -391,654 +506,487 @@ pub fn execute(
 
             ensure!(bid_denom.is_some(), ContractError::InvalidBidDenom {});
 
-            let categories = launch.clone().tokenomics.unwrap().categories;
-
-            let sale_category = categories
-                .iter()
-                .find(|c| c.category_type == CategoryTypes::Sale)
-                .unwrap();
-
-            let launch_min_raise_amount = sale_category.recipients[0].amount.mul(sale.price).mul(
-                Decimal::from_str(&orca.max_slot.to_string())
-                    .unwrap()
-                    .mul(orca.premium_rate_per_slot),
+            // ensure the sale sells a recipient of a sale category and has not been started
+            // sales are scheduled in order and can be rescheduled until they are started
+            let round = round.unwrap_or(0);
+            let allocations = launch.sale_allocations();
+            let sale_idx = launch.sale_index(round, recipient);
+            ensure!(
+                sale_idx.map_or(false, |sale_idx| sale_idx <= launch.pilots.len()
+                    && launch
+                        .pilots
+                        .get(sale_idx)
+                        .map_or(true, |p| p.idx.is_none())),
+                ContractError::InvalidRound(round.to_string())
             );
+            let sale_idx = sale_idx.unwrap();
+            let (_, sale_amount) = allocations[sale_idx];
+
+            // apply the validations of Pilot and Orca up front instead of failing at PilotStart
+            let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
+                config.pilot.pilot_contract.clone(),
+                &kujira_pilot::QueryMsg::Config {},
+            )?;
+            validate_pilot_sale(&config.pilot.sale_bounds, &env.block.time, &sale, &orca)?;
+
+            // the raise when every token sells at the highest premium has to clear the minimum
+            // raise
+            let estimate = raise::estimate(
+                sale_amount,
+                sale.price,
+                launch.clone().token.unwrap().decimals,
+                bid_denom.unwrap().decimals,
+                &orca,
+                pilot_config.sale_fee,
+            )?;
 
             ensure!(
-                launch_min_raise_amount > config.pilot.min_raise_amount,
+                estimate.min_raise >= config.pilot.min_raise_amount,
                 ContractError::InvalidRaiseAmount(
-                    launch_min_raise_amount.to_string(),
+                    estimate.min_raise.to_string(),
                     config.pilot.min_raise_amount.to_string()
                 )
             );
 
+            // ensure that existing FIN pairs trade the token against the bid denom
+            // an existing pair replaces the pair of the first sale's bid denom
+            let denom = launch.clone().token.unwrap().denom;
+            if let Some(fin) = existing_fin.clone() {
+                ensure!(
+                    sale_idx == 0 || launch.pilots[0].orca.bid_denom == orca.bid_denom,
+                    ContractError::InvalidExistingFin(fin.to_string())
+                );
+                post_launch::validate_existing_fin(
+                    deps.as_ref(),
+                    &fin,
+                    None,
+                    &denom,
+                    &orca.bid_denom,
+                )?;
+            }
+            // an existing BOW pool serves a single market, so its launch raises in one bid denom
+            for target in post_launch::liquidity_targets(&launch.clone().tokenomics.unwrap()) {
+                if let LiquidityDestination::ExistingFin { fin, bow } = target.destination {
+                    ensure!(
+                        sale_idx == 0 || launch.pilots[0].orca.bid_denom == orca.bid_denom,
+                        ContractError::InvalidExistingFin(fin.to_string())
+                    );
+                    post_launch::validate_existing_fin(
+                        deps.as_ref(),
+                        &fin,
+                        Some(&bow),
+                        &denom,
+                        &orca.bid_denom,
+                    )?;
+                }
+            }
+
             let mut pilot = Pilot {
                 idx: None,
                 beneficiary: sale.beneficiary.clone(),
                 sale,
                 orca,
+                orca_address: None,
                 bid_pools_snapshot: None,
+                result: None,
             };
             pilot.sale.beneficiary = env.contract.address;
 
-            launch.pilot = Some(pilot);
-            launch.status = LaunchStatus::Planned;
+            if sale_idx < launch.pilots.len() {
+                launch.pilots[sale_idx] = pilot;
+            } else {
+                launch.pilots.push(pilot);
+            }
+            if sale_idx == 0 || existing_fin.is_some() {
+                launch.existing_fin = existing_fin;
+            }
+            // the market parameters apply to the whole launch, later sales keep them unless set
+            if let Some(market) = market.clone() {
+                validate_market_params(deps.api, &config, &market)?;
+            }
+            if sale_idx == 0 || market.is_some() {
+                launch.market_params = market;
+            }
             launch.save(deps.storage)?;
 
             Ok(Response::default()
                 .add_attribute("action", "pilot_schedule")
-                .add_attribute("idx", idx))
+                .add_attribute("idx", idx)
+                .add_attribute("round", round.to_string())
+                .add_attribute("sale", sale_idx.to_string()))
         }
-        ExecuteMsg::PilotStart { idx } => {
-            // Starts the pilot sale by creating the sale on the pilot contract and sets the status to in progress
-            let mut launch = Launch::load(deps.storage, idx)?;
+        ExecuteMsg::PilotStart { idx, round } => {
+            // Starts the pilot sales of the next round by creating them on the pilot contract and sets the status to sale live
+            let launch = Launch::load(deps.storage, idx)?;
             launch.is_owner(&info.sender)?;
-
-            let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
-                config.pilot.pilot_contract.clone(),
-                &kujira_pilot::QueryMsg::Config {},
-            )?;
-
+            start_round(deps, &env, &info, &config, launch, round)
+        }
+        ExecuteMsg::KeeperStart { idx } => {
+            // Starts the next round of a managed token launch for anyone once its sale has opened
+            // The keeper reward is paid out of the funds the owner prefunded, the launch deposit is
+            // forwarded to Pilot
+            // Rounds of several sales need the owner's Pilot deposits of the other sales
+            let mut launch = Launch::load(deps.storage, idx)?;
+            let next_round = launch.started_round().map_or(0, |r| r + 1);
             ensure!(
-                launch.token.clone().is_some()
-                    && launch.tokenomics.clone().is_some()
-                    && launch.pilot.clone().is_some()
-                    && launch.status == LaunchStatus::Planned
-                    && launch.pilot.clone().unwrap().idx.is_none()
-                    && launch.pilot.clone().unwrap().sale.opens.seconds()
-                        <= env.block.time.seconds(),
-                ContractError::Unauthorized {}
+                launch
+                    .sale_allocations()
+                    .iter()
+                    .filter(|(round, _)| *round == next_round)
+                    .count()
+                    <= 1,
+                ContractError::MultiSaleKeeperRound(next_round.to_string())
             );
-
-            let categories = launch.clone().tokenomics.unwrap().categories;
-
-            let sale_category = categories
-                .iter()
-                .find(|c| c.category_type == CategoryTypes::Sale)
-                .unwrap();
-
-            let mut messages = vec![];
-            let denom = launch.clone().token.unwrap().denom;
-
-            let sale_funds = if launch.clone().token.unwrap().is_managed {
-                messages.push(SubMsg::new(CosmosMsg::Custom(KujiraMsg::Denom(
-                    DenomMsg::Mint {
-                        denom: denom.clone(),
-                        amount: sale_category.recipients[0].amount,
-                        recipient: env.contract.address.clone(),
-                    },
-                ))));
-
-                coin(sale_category.recipients[0].amount.u128(), denom.to_string())
-            } else {
-                ensure!(
-                    info.funds.len() == 1,
-                    ContractError::Payment(PaymentError::MultipleDenoms {})
-                );
-
-                let launch_balance = info.funds[0].clone();
-
-                ensure!(
-                    sale_category.recipients[0].amount == launch_balance.amount,
-                    ContractError::InvalidFunds {}
-                );
-
-                launch_balance
-            };
-
-            let bid_denom =
-                config.pilot.allowed_bid_denoms.iter().find(|d| {
-                    d.denom == launch.clone().pilot.clone().unwrap().orca.bid_denom.clone()
-                });
-
-            let sale = if launch.clone().token.unwrap().decimals > bid_denom.unwrap().decimals {
-                let mut sale = launch.clone().pilot.clone().unwrap().sale;
-                sale.price /= Decimal::from_str("10").unwrap().pow(
-                    (launch.clone().token.unwrap().decimals - bid_denom.unwrap().decimals) as u32,
-                );
-                sale
+            let round = launch
+                .keeper_round(&env.block.time)
+                .ok_or(ContractError::NotReadyToStart(idx.to_string()))?;
+            ensure!(info.funds.is_empty(), ContractError::InvalidFunds {});
+
+            let denom = launch.deposit.denom.clone();
+            let prefunded = NativeBalance(std::mem::take(&mut launch.prefunded));
+            let reward = config.launch.keeper_reward.min(
+                prefunded
+                    .0
+                    .iter()
+                    .find(|c| c.denom == denom)
+                    .map_or(Uint128::zero(), |c| c.amount),
+            );
+            let mut prefunded = if reward.is_zero() {
+                prefunded
             } else {
-                launch.pilot.clone().unwrap().sale
+                prefunded.sub(coin(reward.u128(), denom.clone()))?
             };
+            prefunded.normalize();
+            launch.prefunded = prefunded.into_vec();
+            launch.keeper_rewards += reward;
+
+            let mut response = start_round(deps, &env, &info, &config, launch, Some(round))?;
+            if !reward.is_zero() {
+                response =
+                    response
+                        .add_attribute("keeper_reward", reward)
+                        .add_message(BankMsg::Send {
+                            to_address: info.sender.to_string(),
+                            amount: vec![coin(reward.u128(), denom)],
+                        });
+            }
+            Ok(response)
+        }
+        ExecuteMsg::PilotExecute { idx, round } => {
+            // Executes the pilot sales of the live round and sets the status to sale executed
+            // The sale results are recorded in the replies once Pilot has executed the sales
+            let mut launch = Launch::load(deps.storage, idx)?;
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::PilotExecute)?;
+            let (round, sales) = launch.round_sales(round)?;
+            ensure!(
+                Some(round) == launch.started_round(),
+                ContractError::InvalidRound(round.to_string())
+            );
 
-            messages.push(SubMsg::reply_on_success(
-                CosmosMsg::Wasm(wasm_execute(
-                    config.pilot.pilot_contract,
-                    &kujira_pilot::ExecuteMsg::Create {
-                        sale,
-                        orca: launch.pilot.clone().unwrap().orca,
-                    },
-                    vec![pilot_config.deposit, sale_funds],
-                )?),
-                ReplyTypes::Create as u64,
-            ));
-
-            REPLY.save(
-                deps.storage,
-                &ReplyInfo {
-                    reply_type: ReplyTypes::Create,
-                    idx,
-                },
-            )?;
-
-            launch.status = LaunchStatus::InProgress;
-            launch.save(deps.storage)?;
+            let messages = execute_round(deps, &config, &mut launch, &sales, ReplyTypes::Execute)?;
 
             Ok(Response::default()
-                .add_attribute("action", "pilot_start")
+                .add_attribute("action", "pilot_execute")
                 .add_attribute("idx", idx)
+                .add_attribute("round", round.to_string())
                 .add_submessages(messages))
         }
-        ExecuteMsg::PilotExecute { idx } => {
-            // Executes the pilot sale and sets the status to completed
+        ExecuteMsg::Settle { idx } => {
+            // Executes the pilot sales of the last round and performs the post launch actions in the
+            // reply of the last sale, so the liquidity is deployed in the same transaction
+            // The funds of PostLaunch are sent with Settle
             let mut launch = Launch::load(deps.storage, idx)?;
-
+            launch.is_owner(&info.sender)?;
+            let (round, sales) = launch.round_sales(None)?;
             ensure!(
-                launch.status == LaunchStatus::InProgress,
-                ContractError::Unauthorized {}
+                launch.pilots.len() == launch.sale_allocations().len()
+                    && launch.sale_allocations().iter().all(|(r, _)| *r <= round),
+                ContractError::InvalidRound(round.to_string())
             );
 
-            let execute = CosmosMsg::Wasm(wasm_execute(
-                config.clone().pilot.pilot_contract,
-                &kujira_pilot::ExecuteMsg::Execute {
-                    idx: launch.pilot.clone().unwrap().idx.unwrap(),
-                },
-                vec![],
-            )?);
-
-            let mut pilot = launch.clone().pilot.unwrap();
+            // The markets of the live round are counted in the BOW creation fee
+            let bow_fee = post_launch::bow_creation_fee(&config, &launch);
+            ensure_post_launch_deposit(&launch, bow_fee, info.funds)?;
 
-            let sale: kujira_pilot::SaleResponse = deps.querier.query_wasm_smart(
-                config.pilot.pilot_contract.clone(),
-                &kujira_pilot::QueryMsg::Sale {
-                    idx: launch.pilot.clone().unwrap().idx.unwrap(),
-                },
-            )?;
-
-            let bid_pools_result: Result<BidPoolsResponse, StdError> =
-                deps.querier.query_wasm_smart(
-                    sale.orca_address.clone(),
-                    &kujira_orca::QueryMsg::BidPools {
-                        start_after: None,
-                        limit: Some(100),
-                    },
-                );
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::PilotExecute)?;
+            let messages = execute_round(deps, &config, &mut launch, &sales, ReplyTypes::Settle)?;
 
-            pilot.bid_pools_snapshot = if let Ok(bid_pools) = bid_pools_result {
-                Some(bid_pools)
+            Ok(Response::default()
+                .add_attribute("action", "settle")
+                .add_attribute("idx", idx)
+                .add_attribute("round", round.to_string())
+                .add_submessages(messages))
+        }
+        ExecuteMsg::PilotRetract { idx, round } => {
+            // Retracts the pilot sales of the live round
+            // The launch is retracted unless an earlier round has been executed, then it continues
+            // with the executed rounds
+            // The refunds are sent in the replies once Pilot has returned the sale funds
+            let mut launch = Launch::load(deps.storage, idx)?;
+            launch.is_owner(&info.sender)?;
+            let action = if launch.pilots.iter().any(|p| p.result.is_some()) {
+                LaunchAction::RoundRetract
             } else {
-                None
+                LaunchAction::PilotRetract
             };
+            launch.transition(deps.storage, &env, &info.sender, action)?;
+            let (round, sales) = launch.round_sales(round)?;
+            ensure!(
+                Some(round) == launch.started_round(),
+                ContractError::InvalidRound(round.to_string())
+            );
+
+            let mut messages = vec![];
+            for sale_idx in sales {
+                let reply = ReplyInfo {
+                    reply_type: ReplyTypes::Retract,
+                    idx,
+                    sale: sale_idx as u8,
+                };
+                messages.push(SubMsg::reply_on_success(
+                    CosmosMsg::Wasm(wasm_execute(
+                        config.pilot.pilot_contract.clone(),
+                        &kujira_pilot::ExecuteMsg::Retract {
+                            idx: launch.pilots[sale_idx].idx.unwrap(),
+                        },
+                        vec![],
+                    )?),
+                    reply.id()?,
+                ));
+                REPLIES.save(deps.storage, reply.id()?, &reply)?;
+            }
 
-            launch.pilot = Some(pilot);
-            launch.status = LaunchStatus::Completed;
             launch.save(deps.storage)?;
 
             Ok(Response::default()
-                .add_attribute("action", "pilot_execute")
+                .add_attribute("action", "pilot_retract")
                 .add_attribute("idx", idx)
-                .add_message(execute))
+                .add_attribute("round", round.to_string())
+                .add_submessages(messages))
         }
-        ExecuteMsg::PilotRetract { idx } => {
-            // Retracts the pilot sale and sets the status to completed
-            // Changes the denom admin back to the owner if the token is managed
+        ExecuteMsg::CancelLaunch { idx } => {
+            // Cancels the launch before the pilot sale is started and sets the status to cancelled
+            // Refunds the deposit and the prefunded funds and changes the denom admin back to the owner if the token is managed
             let mut launch = Launch::load(deps.storage, idx)?;
             launch.is_owner(&info.sender)?;
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::Cancel)?;
 
             ensure!(
-                launch.status.clone() == LaunchStatus::InProgress,
-                ContractError::Unauthorized {}
+                launch.pilots.iter().all(|p| p.idx.is_none()),
+                ContractError::InvalidStatus {}
             );
 
             let mut messages = vec![];
 
-            messages.push(CosmosMsg::Wasm(wasm_execute(
-                config.pilot.pilot_contract.clone(),
-                &kujira_pilot::ExecuteMsg::Retract {
-                    idx: launch.clone().pilot.unwrap().idx.unwrap(),
-                },
-                vec![],
-            )?));
-
-            if launch.clone().token.unwrap().is_managed {
-                messages.push(CosmosMsg::Custom(KujiraMsg::Denom(DenomMsg::ChangeAdmin {
-                    denom: launch.clone().token.unwrap().denom,
-                    address: launch.owner.clone(),
-                })));
+            if let Some(token) = launch.clone().token {
+                if token.is_managed {
+                    messages.push(CosmosMsg::Custom(KujiraMsg::Denom(DenomMsg::ChangeAdmin {
+                        denom: token.denom,
+                        address: launch.owner.clone(),
+                    })));
+                }
             }
 
-            let categories = launch.clone().tokenomics.unwrap().categories;
-
-            let sale_category = categories
-                .iter()
-                .find(|c| c.category_type == CategoryTypes::Sale)
-                .unwrap();
-
-            let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
-                config.pilot.pilot_contract.clone(),
-                &kujira_pilot::QueryMsg::Config {},
-            )?;
-
-            let mut amount = NativeBalance::default();
-            amount.add_assign(coin(
-                sale_category.recipients[0].amount.u128(),
-                launch.clone().token.unwrap().denom.to_string(),
-            ));
-            amount.add_assign(pilot_config.deposit);
-            amount.normalize();
-
-            messages.push(CosmosMsg::Bank(BankMsg::Send {
-                to_address: launch.owner.to_string(),
-                amount: amount.into_vec(),
-            }));
+            let mut refund = NativeBalance(std::mem::take(&mut launch.prefunded));
+            refund.add_assign(launch.deposit.clone());
+            refund.normalize();
+            if !refund.is_empty() {
+                messages.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: launch.owner.to_string(),
+                    amount: refund.into_vec(),
+                }));
+            }
 
-            launch.status = LaunchStatus::Completed;
             launch.save(deps.storage)?;
 
             Ok(Response::default()
-                .add_attribute("action", "pilot_retract")
+                .add_attribute("action", "cancel_launch")
                 .add_attribute("idx", idx)
                 .add_messages(messages))
         }
         ExecuteMsg::PostLaunch { idx } => {
-            // Executes the post launch actions and sets the status to completed
+            // Executes the post launch actions and sets the status to liquidity deployed
             // Sets up the vesting schedules
             // Creates the FIN and BOW contracts
             // Changes the denom admin to the specified address
             let mut launch = Launch::load(deps.storage, idx)?;
             launch.is_owner(&info.sender)?;
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::PostLaunch)?;
 
-            // InProgress is allowed for when Execute is called on the Pilot Contract directly
-            ensure!(
-                (launch.status == LaunchStatus::InProgress
-                    || launch.status == LaunchStatus::Completed),
-                ContractError::Unauthorized {}
-            );
+            // Ensure the denom fee of every BOW created is paid
+            let bow_fee = post_launch::bow_creation_fee(&config, &launch);
 
-            //Ensure amount deposited is correct for non managed tokens
-            if !launch.clone().token.unwrap().is_managed {
-                let mut non_managed_amount = Uint128::zero();
-                for category in launch.clone().tokenomics.unwrap().categories {
-                    if category.category_type != CategoryTypes::Sale {
-                        for recipient in category.recipients {
-                            non_managed_amount = non_managed_amount.add(recipient.amount);
-                        }
-                    }
-                }
-                let mut balances = NativeBalance(info.funds);
-                balances = balances.sub(config.token.denom_fee.clone())?;
-                let balances = balances.into_vec();
-                ensure!(balances.len() == 1, ContractError::InvalidFunds {});
-                ensure!(
-                    non_managed_amount == balances[0].amount,
-                    ContractError::InvalidDeposit(
-                        non_managed_amount.to_string(),
-                        balances[0].amount.to_string()
-                    )
-                );
-            } else {
-                ensure!(info.funds.len() == 1, ContractError::InvalidFunds {});
+            ensure_post_launch_deposit(&launch, bow_fee, info.funds)?;
+
+            // Prefunded fees are not needed when the owner pays them
+            let prefunded = std::mem::take(&mut launch.prefunded);
+            let mut response = deploy_liquidity(deps, &env, &config, launch.clone())?;
+            if !prefunded.is_empty() {
+                response = response.add_message(BankMsg::Send {
+                    to_address: launch.owner.to_string(),
+                    amount: prefunded,
+                });
+            }
+            Ok(response)
+        }
+        ExecuteMsg::PricePrecision {
+            idx,
+            decimal_places,
+        } => {
+            // Overrides the decimal places of the FIN prices set up in PostLaunch
+            let mut launch = Launch::load(deps.storage, idx)?;
+            launch.is_owner(&info.sender)?;
+            launch.transition(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::PricePrecision,
+            )?;
+
+            if let Some(decimal_places) = decimal_places {
+                let policy = &config.fin.price_precision;
                 ensure!(
-                    info.funds[0] == config.token.denom_fee,
-                    ContractError::InvalidDeposit(
-                        config.token.denom_fee.amount.to_string(),
-                        info.funds[0].amount.to_string()
-                    )
+                    decimal_places >= policy.min_decimal_places
+                        && decimal_places <= policy.max_decimal_places,
+                    ContractError::InvalidPricePrecision(decimal_places.to_string())
                 );
             }
 
-            let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
-                config.pilot.pilot_contract.clone(),
-                &kujira_pilot::QueryMsg::Config {},
-            )?;
+            launch.price_precision = decimal_places;
+            launch.save(deps.storage)?;
 
-            // Only allow filled sales to execute this message
-            let pilot_sale: StdResult<kujira_pilot::SaleResponse> = deps.querier.query_wasm_smart(
-                config.pilot.pilot_contract.clone(),
-                &kujira_pilot::QueryMsg::Sale {
-                    idx: launch.clone().pilot.unwrap().idx.unwrap(),
-                },
+            Ok(Response::default()
+                .add_attribute("action", "price_precision")
+                .add_attribute("idx", idx))
+        }
+        ExecuteMsg::Prefund { idx } => {
+            // Adds the funds sent to the prefunded BOW creation fee and keeper rewards of the launch
+            let mut launch = Launch::load(deps.storage, idx)?;
+            launch.is_owner(&info.sender)?;
+            ensure!(
+                !launch.status.is_terminal() && launch.status != LaunchStatus::LiquidityDeployed,
+                ContractError::InvalidStatus {}
             );
+            ensure!(!info.funds.is_empty(), ContractError::InvalidFunds {});
 
-            let (raise_total, raise_amount) = if let Status::Executed {
-                at: _at,
-                raise_total,
-                raise_fee: _raise_fee,
-                raise_amount,
-            } = pilot_sale.unwrap().status
-            {
-                (raise_total, raise_amount)
-            } else {
-                return Err(ContractError::Unauthorized {});
-            };
+            let mut prefunded = NativeBalance(launch.prefunded.clone()) + NativeBalance(info.funds);
+            prefunded.normalize();
+            launch.prefunded = prefunded.into_vec();
+            launch.save(deps.storage)?;
+            launch.record(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::Prefund,
+                launch.status.clone(),
+            )?;
 
-            let mut messages = vec![];
+            Ok(Response::default()
+                .add_attribute("action", "prefund")
+                .add_attribute("idx", idx))
+        }
+        ExecuteMsg::CrankPostLaunch { idx } => {
+            // Executes the post launch actions of a managed token launch for anyone once every round
+            // has been started and executed or retracted, the BOW creation fee is paid from the
+            // prefunded funds and the rest of them is returned to the owner
+            let mut launch = Launch::load(deps.storage, idx)?;
+            ensure!(
+                launch.clone().token.map_or(false, |t| t.is_managed),
+                ContractError::Unauthorized {}
+            );
+            ensure!(
+                launch.all_rounds_started(),
+                ContractError::NotAwaitingPostLaunch(idx.to_string())
+            );
+            ensure!(info.funds.is_empty(), ContractError::InvalidFunds {});
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::PostLaunch)?;
+
+            let bow_fee = post_launch::bow_creation_fee(&config, &launch);
+            let mut remaining = NativeBalance(std::mem::take(&mut launch.prefunded));
+            if !bow_fee.amount.is_zero() {
+                remaining = remaining
+                    .sub(bow_fee.clone())
+                    .map_err(|_| ContractError::InsufficientPrefund(bow_fee.to_string()))?;
+            }
+            remaining.normalize();
 
-            let tokenomics = launch.clone().tokenomics.unwrap();
-            let denom = launch.clone().token.unwrap().denom;
-            let denom_symbol = launch.clone().token.unwrap().symbol;
-            let bid_denom = launch.clone().pilot.unwrap().orca.bid_denom;
-            let bid_denom_config = config
-                .pilot
-                .allowed_bid_denoms
-                .iter()
-                .find(|d| d.denom == bid_denom)
-                .unwrap();
-
-            // Setup categories of tokenomcs and vesting schedules
-            for category in tokenomics.categories.clone() {
-                if category.category_type == CategoryTypes::Standard {
-                    for recipient in category.recipients {
-                        if let Some(flows) = recipient.flows {
-                            if launch.clone().token.unwrap().is_managed {
-                                messages.push(CosmosMsg::Custom(KujiraMsg::Denom(
-                                    DenomMsg::Mint {
-                                        denom: denom.clone(),
-                                        amount: recipient.amount,
-                                        recipient: env.contract.address.clone(),
-                                    },
-                                )));
-                            };
-                            for flow in flows {
-                                let mut flows_amount = 0u128;
-                                for schedule in flow.clone().schedules {
-                                    flows_amount = flows_amount.add(schedule.amount.u128());
-                                }
-                                messages.push(CosmosMsg::Wasm(wasm_execute(
-                                    &config.flows.flows_contract,
-                                    &fuzion_flows::ExecuteMsg::CreateFlows {
-                                        flow_list: vec![flow],
-                                    },
-                                    coins(flows_amount, denom.to_string()),
-                                )?));
-                            }
-                        } else if launch.clone().token.unwrap().is_managed {
-                            messages.push(CosmosMsg::Custom(KujiraMsg::Denom(DenomMsg::Mint {
-                                denom: denom.clone(),
-                                amount: recipient.amount,
-                                recipient: recipient.address.unwrap(),
-                            })));
-                        } else {
-                            messages.push(CosmosMsg::Bank(BankMsg::Send {
-                                amount: coins(recipient.amount.u128(), denom.clone().to_string()),
-                                to_address: recipient.address.unwrap().to_string(),
-                            }));
-                        }
-                    }
-                }
-            }
-
-            // Setup FIN Pair Contract
-            let CodeInfoResponse { checksum, .. } =
-                deps.querier.query_wasm_code_info(config.fin.code_id)?;
-
-            let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
-
-            let fin_salt = Binary::from(format!("keiko_fin_{idx}").as_bytes());
-            let fin_address = deps
-                .api
-                .addr_humanize(&instantiate2_address(&checksum, &creator, &fin_salt).unwrap())?;
-
-            // get the Sale Tokenomics category
-            let sale_category = tokenomics
-                .categories
-                .iter()
-                .find(|c| c.category_type == CategoryTypes::Sale)
-                .unwrap();
-
-            let decimal_delta = launch.clone().token.unwrap().decimals - bid_denom_config.decimals;
-            let average_price_of_launch = if decimal_delta > 0 {
-                Decimal256::from_ratio(
-                    raise_amount
-                        .checked_mul(Uint128::from_str("10").unwrap().pow(decimal_delta as u32))
-                        .unwrap(),
-                    sale_category.recipients[0].amount,
-                )
-            } else {
-                Decimal256::from_ratio(raise_amount, sale_category.recipients[0].amount)
-            };
-
-            let price_precision_decimals = if average_price_of_launch.lt(&Decimal256::one()) {
-                let num_str = average_price_of_launch.to_string();
-                let parts: Vec<&str> = num_str.split('.').collect();
-                parts[1].chars().take_while(|&c| c == '0').count() + 5
-            } else if average_price_of_launch.ge(&Decimal256::one())
-                && average_price_of_launch.lt(&Decimal256::from_str("1000.0")?)
-            {
-                4
-            } else {
-                3
-            };
-
-            let fin = CosmosMsg::Wasm(WasmMsg::Instantiate2 {
-                admin: Some(config.fin.admin.clone().to_string()),
-                code_id: config.fin.code_id,
-                msg: to_json_binary(&kujira_fin::InstantiateMsg {
-                    owner: env.contract.address.clone(),
-                    denoms: [
-                        cw20::Denom::Native(denom.to_string()),
-                        cw20::Denom::Native(bid_denom.to_string()),
-                    ],
-                    decimal_delta: Some((decimal_delta) as i8),
-                    price_precision: Precision::DecimalPlaces(price_precision_decimals as u8),
-                    fee_maker: config.fin.fee_maker,
-                    fee_taker: config.fin.fee_taker,
-                    fee_address: config.fin.fee_address,
-                })?,
-                funds: vec![],
-                label: format!("FIN {}-{}", denom_symbol, bid_denom_config.symbol),
-                salt: fin_salt,
-            });
-
-            messages.push(fin);
-
-            // Setup BOW Market Maker Contract
-            let CodeInfoResponse { checksum, .. } =
-                deps.querier.query_wasm_code_info(config.bow.code_id)?;
-
-            let bow_salt = Binary::from(format!("keiko_bow_{idx}").as_bytes());
-            let bow_address = deps
-                .api
-                .addr_humanize(&instantiate2_address(&checksum, &creator, &bow_salt).unwrap())?;
-
-            let bow = CosmosMsg::Wasm(WasmMsg::Instantiate2 {
-                admin: Some(config.bow.admin.clone().to_string()),
-                code_id: config.bow.code_id,
-                msg: to_json_binary(&kujira::bow::market_maker::InstantiateMsg {
-                    owner: config.bow.owner.clone(),
-                    fin_contract: fin_address.clone(),
-                    intervals: config.bow.intervals,
-                    fee: config.bow.fee,
-                    amp: config.bow.amp,
-                })?,
-                funds: vec![config.token.denom_fee.clone()],
-                label: format!("Bow: {}-{}", denom_symbol, bid_denom_config.symbol),
-                salt: bow_salt,
-            });
-
-            messages.push(bow);
-
-            // Provide liquidity to BOW Market Maker
-            // get the Liquidity Tokenomics category
-            let lp_category = tokenomics
-                .categories
-                .iter()
-                .find(|c| c.category_type == CategoryTypes::Liquidity)
-                .unwrap();
-
-            // calculate the LP to provide to the pool
-            if launch.clone().token.unwrap().is_managed {
-                messages.push(CosmosMsg::Custom(KujiraMsg::Denom(DenomMsg::Mint {
-                    denom: denom.clone(),
-                    amount: lp_category.recipients[0].amount,
-                    recipient: env.contract.address.clone(),
-                })));
-            };
-            let lp_denom_funds = coin(lp_category.recipients[0].amount.u128(), denom.to_string());
-
-            let lp_stable_amount = raise_total.multiply_ratio(
-                lp_category.recipients[0].amount.u128(),
-                sale_category.recipients[0].amount.u128(),
-            );
-            let lp_stable_funds = coin(lp_stable_amount.u128(), bid_denom.to_string());
-
-            let mut lp_funds = NativeBalance(vec![lp_denom_funds.clone(), lp_stable_funds]);
-            lp_funds.normalize();
-            let lp_funds = lp_funds.into_vec();
-            let liquidity = CosmosMsg::Wasm(wasm_execute(
-                bow_address.clone(),
-                &kujira::bow::market_maker::execute::ExecuteMsg::Deposit {
-                    max_slippage: None,
-                    callback: Some(to_json_binary(&CallbackType::BowCallback { idx })?.into()),
-                },
-                lp_funds,
-            )?);
-
-            messages.push(liquidity);
-
-            if launch.clone().token.unwrap().is_managed {
-                let denom_admin =
-                    if let Some(denom_admin) = launch.clone().token.unwrap().denom_admin {
-                        denom_admin
-                    } else {
-                        config.token.default_admin
-                    };
-                // Change the denom admin
-                messages.push(CosmosMsg::Custom(KujiraMsg::Denom(DenomMsg::ChangeAdmin {
-                    denom,
-                    address: denom_admin,
-                })));
-
-                // Register the token in Fuzion Products
-                let managed_token = launch.clone().token.unwrap();
-                let logo_uris: Option<LogoURIs> = Some(LogoURIs {
-                    png: managed_token.png_url,
-                    svg: managed_token.svg_url,
-                });
-                let asset = AssetList {
-                    chain_name: "kujira".to_string(),
-                    assets: vec![Asset {
-                        description: Some(format!("{} Token", managed_token.symbol).to_string()),
-                        denom_units: vec![
-                            DenomUnit {
-                                denom: managed_token.denom.to_string(),
-                                exponent: 0,
-                            },
-                            DenomUnit {
-                                denom: managed_token.symbol.to_string().to_lowercase(),
-                                exponent: managed_token.decimals as u16,
-                            },
-                        ],
-                        base: managed_token.denom.to_string(),
-                        name: managed_token.symbol.to_string(),
-                        display: managed_token.symbol.to_string().to_lowercase(),
-                        symbol: managed_token.symbol.to_string(),
-                        coingecko_id: None,
-                        logo_uris,
-                    }],
-                };
-
-                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: config.token.utilities_contract.to_string(),
-                    msg: to_json_binary(&fuzion_utilities::ExecuteMsg::UploadAsset { asset })?,
-                    funds: vec![],
-                }));
-
-                let coin = Coin {
-                    denom: managed_token.denom.to_string(),
-                    amount: Uint128::from(0_u128),
-                };
-
-                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: config.token.utilities_contract.to_string(),
-                    msg: to_json_binary(&fuzion_utilities::ExecuteMsg::UploadTotalSupply {
-                        total_supply_list: vec![coin.clone()],
-                    })?,
-                    funds: vec![],
-                }));
-
-                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: config.token.utilities_contract.to_string(),
-                    msg: to_json_binary(&fuzion_utilities::ExecuteMsg::UploadCuratedDenoms {
-                        curated_denom_list: vec![coin],
-                    })?,
-                    funds: vec![],
-                }));
-            }
-
-            if !pilot_config.deposit.amount.is_zero() {
-                messages.push(CosmosMsg::Bank(BankMsg::Send {
+            let mut response = deploy_liquidity(deps, &env, &config, launch.clone())?;
+            if !remaining.is_empty() {
+                response = response.add_message(BankMsg::Send {
                     to_address: launch.owner.to_string(),
-                    amount: vec![pilot_config.deposit],
-                }));
-            }
-
-            let beneficiary_funds = coin(
-                raise_amount.u128().sub(lp_stable_amount.u128()),
-                bid_denom.to_string(),
-            );
-
-            if !beneficiary_funds.amount.is_zero() {
-                messages.push(CosmosMsg::Bank(BankMsg::Send {
-                    to_address: launch.clone().pilot.unwrap().beneficiary.to_string(),
-                    amount: vec![beneficiary_funds],
-                }));
+                    amount: remaining.into_vec(),
+                });
             }
-
-            launch.fin = Some(Fin {
-                contract_address: Some(fin_address),
-            });
-
-            launch.bow = Some(Bow {
-                contract_address: Some(bow_address),
-            });
-
-            launch.status = LaunchStatus::Completed;
-            launch.save(deps.storage)?;
-
-            Ok(Response::default()
-                .add_attribute("action", "post_execute")
-                .add_attribute("idx", idx)
-                .add_messages(messages))
+            Ok(response)
         }
         ExecuteMsg::Update { launch } => {
             ensure!(info.sender == config.owner, ContractError::Unauthorized {});
-            let _ = Launch::load(deps.storage, launch.clone().idx)?;
+            let existing = Launch::load(deps.storage, launch.clone().idx)?;
             launch.save(deps.storage)?;
+            launch.record(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::Update,
+                existing.status,
+            )?;
             Ok(Response::default().add_attribute("action", "update"))
         }
         ExecuteMsg::LaunchFin { idx } => {
-            let launch = Launch::load(deps.storage, idx)?;
+            let mut launch = Launch::load(deps.storage, idx)?;
             ensure!(
                 info.sender == config.owner
                     || info.sender == config.fin.owner
                     || info.sender == launch.owner,
                 ContractError::Unauthorized {}
             );
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::LaunchFin)?;
 
             ensure!(
-                (launch.status == LaunchStatus::Completed
-                    && launch.fin.is_some()
-                    && launch.bow.is_some()),
+                launch.fin.is_some() && launch.bow.is_some(),
                 ContractError::Unauthorized {}
             );
+            launch.save(deps.storage)?;
 
-            let mut messages = vec![CosmosMsg::Wasm(wasm_execute(
-                launch.clone().fin.unwrap().contract_address.unwrap(),
-                &kujira_fin::ExecuteMsg::Launch {},
-                vec![],
-            )?)];
-
-            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: launch.fin.unwrap().contract_address.unwrap().to_string(),
-                msg: to_json_binary(&kujira_fin::ExecuteMsg::UpdateConfig {
-                    owner: Some(config.fin.owner.clone()),
-                    price_precision: None,
-                    fee_taker: None,
-                    fee_maker: None,
-                })?,
-                funds: vec![],
-            }));
+            // Launches deployed before markets only have the single FIN pair
+            let fins: Vec<Addr> = match launch.markets.clone() {
+                Some(markets) => markets
+                    .into_iter()
+                    .filter(|m| !m.existing)
+                    .map(|m| m.fin)
+                    .collect(),
+                None if launch.existing_fin.is_none() => {
+                    vec![launch.clone().fin.unwrap().contract_address.unwrap()]
+                }
+                None => vec![],
+            };
+
+            // An existing FIN pair is already trading
+            if fins.is_empty() {
+                return Ok(Response::default()
+                    .add_attribute("action", "LaunchFin")
+                    .add_attribute("existing_fin", "true"));
+            }
+
+            let mut messages = vec![];
+            for fin in fins {
+                messages.push(CosmosMsg::Wasm(wasm_execute(
+                    fin.clone(),
+                    &kujira_fin::ExecuteMsg::Launch {},
+                    vec![],
+                )?));
+
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: fin.to_string(),
+                    msg: to_json_binary(&kujira_fin::ExecuteMsg::UpdateConfig {
+                        owner: Some(config.fin.owner.clone()),
+                        price_precision: None,
+                        fee_taker: None,
+                        fee_maker: None,
+                    })?,
+                    funds: vec![],
+                }));
+            }
 
             Ok(Response::default()
                 .add_attribute("action", "LaunchFin")
@@ -1071,24 +1019,282 @@ pub fn execute(
         ExecuteMsg::UpdateDescription { idx, description } => {
             let mut launch = Launch::load(deps.storage, idx)?;
             ensure!(
-                (info.sender == config.owner || info.sender == launch.owner)
-                    && launch.status == LaunchStatus::InProgress,
+                info.sender == config.owner || info.sender == launch.owner,
                 ContractError::Unauthorized {}
             );
-            let mut pilot = launch.clone().pilot.unwrap();
-            pilot.sale.description = description.clone();
-            launch.pilot = Some(pilot.clone());
+            launch.transition(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::UpdateDescription,
+            )?;
+            // updates the description of the sales of the live round
+            let (_, sales) = launch.round_sales(None)?;
+            let mut messages = vec![];
+            for sale_idx in sales {
+                launch.pilots[sale_idx].sale.description = description.clone();
+                messages.push(CosmosMsg::Wasm(wasm_execute(
+                    config.pilot.pilot_contract.clone(),
+                    &kujira_pilot::ExecuteMsg::UpdateSaleDescription {
+                        idx: launch.pilots[sale_idx].idx.unwrap(),
+                        description: description.clone(),
+                    },
+                    vec![],
+                )?));
+            }
             launch.save(deps.storage)?;
             Ok(Response::default()
                 .add_attribute("action", "update")
-                .add_message(CosmosMsg::Wasm(wasm_execute(
-                    config.pilot.pilot_contract.clone(),
-                    &kujira_pilot::ExecuteMsg::UpdateSaleDescription {
-                        idx: pilot.idx.unwrap(),
-                        description,
+                .add_messages(messages))
+        }
+        ExecuteMsg::SetAllowlist { idx, allowlist } => {
+            // sets the allowlist of the launch, bids through AllowlistBid are checked against it
+            let mut launch = Launch::load(deps.storage, idx)?;
+            launch.is_owner(&info.sender)?;
+            launch.transition(deps.storage, &env, &info.sender, LaunchAction::Allowlist)?;
+
+            if let Some(allowlist) = allowlist.clone() {
+                allowlist::decode_hash(&allowlist.merkle_root)?;
+            }
+
+            launch.allowlist = allowlist;
+            launch.save(deps.storage)?;
+
+            Ok(Response::default()
+                .add_attribute("action", "set_allowlist")
+                .add_attribute("idx", idx))
+        }
+        ExecuteMsg::AllowlistBid {
+            idx,
+            premium_slot,
+            cap,
+            proof,
+        } => {
+            // Places the bid on the Orca queue of the live sale raising the denom sent
+            // Keiko owns the bid on Orca, it is recorded for the bidder once Orca returns its idx
+            // The bidder retracts it with RetractAllowlistBid and claims it with ClaimForBidder
+            let launch = Launch::load(deps.storage, idx)?;
+            ensure!(
+                launch.status == LaunchStatus::SaleLive,
+                ContractError::InvalidStatus {}
+            );
+            let allowlist = launch
+                .allowlist
+                .clone()
+                .ok_or(ContractError::NoAllowlist {})?;
+            allowlist::verify(&allowlist, &info.sender, cap, &proof)?;
+
+            let funds = one_coin(&info)?;
+            let (_, sales) = launch.round_sales(None)?;
+            let sale_idx = sales
+                .into_iter()
+                .find(|s| {
+                    launch.pilots[*s].idx.is_some()
+                        && launch.pilots[*s].orca.bid_denom.to_string() == funds.denom
+                })
+                .ok_or(ContractError::InvalidBidDenom {})?;
+
+            // the cap applies to the bids of each bid denom
+            if let Some(cap) = cap {
+                let bid = ALLOWLIST_BIDS
+                    .may_load(deps.storage, (idx.u128(), &info.sender))?
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|b| b.amount.denom == funds.denom)
+                    .fold(funds.amount, |acc, b| acc + b.amount.amount);
+                ensure!(
+                    bid <= cap,
+                    ContractError::AllowlistCapExceeded(cap.to_string(), bid.to_string())
+                );
+            }
+
+            let sale: kujira_pilot::SaleResponse = deps.querier.query_wasm_smart(
+                config.pilot.pilot_contract,
+                &kujira_pilot::QueryMsg::Sale {
+                    idx: launch.pilots[sale_idx].idx.unwrap(),
+                },
+            )?;
+
+            let reply = ReplyInfo {
+                reply_type: ReplyTypes::Bid,
+                idx,
+                sale: sale_idx as u8,
+            };
+            let bid = SubMsg::reply_on_success(
+                CosmosMsg::Wasm(wasm_execute(
+                    sale.orca_address,
+                    &kujira_orca::ExecuteMsg::SubmitBid {
+                        premium_slot,
+                        delegate: None,
+                        proof: None,
+                    },
+                    vec![funds.clone()],
+                )?),
+                reply.id()?,
+            );
+            REPLIES.save(deps.storage, reply.id()?, &reply)?;
+            PENDING_BIDS.save(deps.storage, reply.id()?, &(info.sender.clone(), funds))?;
+            launch.record(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::AllowlistBid,
+                launch.status.clone(),
+            )?;
+
+            Ok(Response::default()
+                .add_attribute("action", "allowlist_bid")
+                .add_attribute("idx", idx)
+                .add_attribute("bidder", info.sender)
+                .add_submessage(bid))
+        }
+        ExecuteMsg::RetractAllowlistBid { idx, sale, bid_idx } => {
+            // Retracts the bid Keiko owns on Orca for the bidder while its sale is live, the
+            // funds come back to Keiko before the transfer to the bidder runs
+            // Bids of executed or retracted sales are claimed with ClaimForBidder, so that the
+            // liquidated collateral of a filled bid is claimed with it
+            let launch = Launch::load(deps.storage, idx)?;
+            let mut bids = ALLOWLIST_BIDS
+                .may_load(deps.storage, (idx.u128(), &info.sender))?
+                .unwrap_or_default();
+            let bid = bids
+                .iter_mut()
+                .find(|b| b.sale == sale && b.bid_idx == bid_idx && !b.claimed)
+                .ok_or(ContractError::NothingToClaim(info.sender.to_string()))?;
+            let pilot = &launch.pilots[bid.sale as usize];
+            ensure!(
+                !sale_closed(&deps.querier, &config, pilot)?,
+                ContractError::InvalidStatus {}
+            );
+
+            let sale: kujira_pilot::SaleResponse = deps.querier.query_wasm_smart(
+                config.pilot.pilot_contract,
+                &kujira_pilot::QueryMsg::Sale {
+                    idx: pilot.idx.unwrap(),
+                },
+            )?;
+            let orca_bid: kujira_orca::BidResponse = deps.querier.query_wasm_smart(
+                sale.orca_address.clone(),
+                &kujira_orca::QueryMsg::Bid { bid_idx },
+            )?;
+            ensure!(
+                !orca_bid.amount.is_zero(),
+                ContractError::NothingToClaim(info.sender.to_string())
+            );
+
+            let refund = coin(orca_bid.amount.u128(), bid.amount.denom.clone());
+            bid.amount.amount = bid.amount.amount.saturating_sub(orca_bid.amount);
+            bid.claimed = true;
+            ALLOWLIST_BIDS.save(deps.storage, (idx.u128(), &info.sender), &bids)?;
+            launch.record(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::RetractAllowlistBid,
+                launch.status.clone(),
+            )?;
+
+            Ok(Response::default()
+                .add_attribute("action", "retract_allowlist_bid")
+                .add_attribute("idx", idx)
+                .add_attribute("bidder", info.sender.clone())
+                .add_attribute("bid_idx", bid_idx)
+                .add_message(wasm_execute(
+                    sale.orca_address,
+                    &kujira_orca::ExecuteMsg::RetractBid {
+                        bid_idx,
+                        amount: None,
                     },
                     vec![],
-                )?)))
+                )?)
+                .add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: vec![refund],
+                }))
+        }
+        ExecuteMsg::MigrateLaunches { limit } => {
+            ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+            ensure!(
+                MIGRATION_CURSOR.may_load(deps.storage)?.is_some(),
+                ContractError::NoMigrationPending {}
+            );
+            let (migrated, pending) = migrate_launches(deps, &env, &info.sender, &config, limit)?;
+
+            Ok(Response::default()
+                .add_attribute("action", "migrate_launches")
+                .add_attribute("migrated_launches", migrated.to_string())
+                .add_attribute("migration_pending", pending.to_string()))
+        }
+        ExecuteMsg::ClaimForBidder { idx, bidder } => {
+            // Claims and retracts the bids Keiko owns on Orca for the bidder, the funds come back
+            // to Keiko before the transfer to the bidder runs
+            // Bids of live sales are left on Orca, the ones of executed and retracted sales are
+            // claimed and retracted
+            let launch = Launch::load(deps.storage, idx)?;
+            let positions: Vec<BidderPosition> =
+                bidder_positions(deps.as_ref(), &config, &launch, &bidder)?
+                    .into_iter()
+                    .filter(|p| p.keiko && !p.live)
+                    .collect();
+            ensure!(
+                !positions.is_empty(),
+                ContractError::NothingToClaim(bidder.to_string())
+            );
+
+            let mut messages: Vec<CosmosMsg<KujiraMsg>> = vec![];
+            let mut funds = NativeBalance::default();
+            for position in positions {
+                if !position.claimable.amount.is_zero() {
+                    messages.push(CosmosMsg::Wasm(wasm_execute(
+                        position.orca_address.clone(),
+                        &kujira_orca::ExecuteMsg::ClaimLiquidations {
+                            bids: Some(vec![position.bid_idx]),
+                        },
+                        vec![],
+                    )?));
+                    funds.add_assign(position.claimable);
+                }
+                if !position.retractable.amount.is_zero() {
+                    messages.push(CosmosMsg::Wasm(wasm_execute(
+                        position.orca_address,
+                        &kujira_orca::ExecuteMsg::RetractBid {
+                            bid_idx: position.bid_idx,
+                            amount: None,
+                        },
+                        vec![],
+                    )?));
+                    funds.add_assign(position.retractable);
+                }
+            }
+            funds.normalize();
+            messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: bidder.to_string(),
+                amount: funds.into_vec(),
+            }));
+
+            // every bid of an executed or retracted sale is done with, the ones left empty included
+            let mut bids = ALLOWLIST_BIDS
+                .may_load(deps.storage, (idx.u128(), &bidder))?
+                .unwrap_or_default();
+            for bid in bids.iter_mut() {
+                if sale_closed(&deps.querier, &config, &launch.pilots[bid.sale as usize])? {
+                    bid.claimed = true;
+                }
+            }
+            ALLOWLIST_BIDS.save(deps.storage, (idx.u128(), &bidder), &bids)?;
+            launch.record(
+                deps.storage,
+                &env,
+                &info.sender,
+                LaunchAction::ClaimForBidder,
+                launch.status.clone(),
+            )?;
+
+            Ok(Response::default()
+                .add_attribute("action", "claim_for_bidder")
+                .add_attribute("idx", idx)
+                .add_attribute("bidder", bidder)
+                .add_messages(messages))
         }
         ExecuteMsg::Callback(msg) => {
             // Executes the callback from the BOW Market Maker
@@ -1097,39 +1303,65 @@ pub fn execute(
             let mut messages = vec![];
             match cb_msg {
                 CallbackType::BowCallback { idx } => {
-                    let launch = Launch::load(deps.storage, idx)?;
-                    ensure!(
-                        info.sender == launch.clone().bow.unwrap().contract_address.unwrap(),
-                        ContractError::Unauthorized {}
-                    );
+                    let mut launch = Launch::load(deps.storage, idx)?;
+                    launch.transition(
+                        deps.storage,
+                        &env,
+                        &info.sender,
+                        LaunchAction::BowCallback,
+                    )?;
                     ensure!(info.funds.len() == 1, ContractError::LPTokensNotReceived {});
 
-                    let lp_flow = FlowCreate {
-                        flow_type: FlowType::Vesting,
-                        taker: launch.clone().pilot.unwrap().beneficiary,
-                        denom: info.funds[0].denom.clone(),
-                        genesis_time: env.block.time.seconds(),
-                        identifier: None,
-                        schedules: vec![FlowSchedule {
-                            start_time: env.block.time.seconds(),
-                            end_time: env.block.time.seconds()
-                                + config.tokenomics.default_lp_vest_duration,
-                            amount: info.funds[0].amount,
-                            cliff_end_time: env.block.time.seconds()
-                                + config.tokenomics.default_lp_vest_cliff,
-                        }],
-                    };
+                    // Each deposit is vested once, launches deployed before liquidity targets
+                    // only have the single BOW contract
+                    if let Some(mut liquidity) = launch.liquidity.clone() {
+                        let position = liquidity
+                            .iter_mut()
+                            .find(|p| p.bow == info.sender && p.lp.is_none())
+                            .ok_or(ContractError::Unauthorized {})?;
+                        position.lp = Some(info.funds[0].clone());
+                        launch.liquidity = Some(liquidity);
+                    } else {
+                        ensure!(
+                            info.sender == launch.clone().bow.unwrap().contract_address.unwrap(),
+                            ContractError::Unauthorized {}
+                        );
+                    }
+                    launch.save(deps.storage)?;
+
+                    // The bid denom of the pool is the one deposited next to the launch token
+                    let token_denom = launch.clone().token.unwrap().denom;
+                    let bid_denom = launch
+                        .clone()
+                        .liquidity
+                        .unwrap_or_default()
+                        .iter()
+                        .find(|p| p.bow == info.sender)
+                        .and_then(|p| {
+                            p.deposit
+                                .iter()
+                                .find(|c| c.denom != token_denom.to_string())
+                                .map(|c| Denom::from(c.denom.clone()))
+                        })
+                        .unwrap_or(launch.pilots[0].orca.bid_denom.clone());
+
+                    let lp_flows = post_launch::lp_flows(
+                        &config,
+                        &launch,
+                        &info.funds[0],
+                        &bid_denom,
+                        env.block.time.seconds(),
+                    );
 
                     messages.push(CosmosMsg::Wasm(wasm_execute(
                         &config.flows.flows_contract,
                         &fuzion_flows::ExecuteMsg::CreateFlows {
-                            flow_list: vec![lp_flow],
+                            flow_list: lp_flows,
                         },
                         info.funds.clone(),
                     )?));
 
                     // Register the LP token in Fuzion Products
-                    let bid_denom = launch.clone().pilot.unwrap().orca.bid_denom;
                     let bid_denom_config = config
                         .pilot
                         .allowed_bid_denoms
@@ -1217,8 +1449,661 @@ pub fn execute(
     }
 }
 
+/// Sends the PostLaunch messages of the launch and records the markets and liquidity it deploys
+fn deploy_liquidity(
+    deps: DepsMut<KujiraQuery>,
+    env: &Env,
+    config: &Config,
+    mut launch: Launch,
+) -> Result<Response<KujiraMsg>, ContractError> {
+    let idx = launch.idx;
+    let plan = post_launch::plan(deps.as_ref(), env, config, &launch)?;
+
+    // New FIN pairs are instantiated with instantiate2 at the address the plan predicted, the
+    // reply verifies the instantiated address
+    let mut markets = vec![];
+    for (m, market) in plan.markets.iter().enumerate() {
+        let existing = launch.existing_fin == Some(market.fin_address.clone());
+        if !existing {
+            let reply = ReplyInfo {
+                reply_type: ReplyTypes::PostExecute,
+                idx,
+                sale: m as u8,
+            };
+            REPLIES.save(deps.storage, reply.id()?, &reply)?;
+        }
+        markets.push(Market {
+            bid_denom: market.bid_denom.clone(),
+            fin: market.fin_address.clone(),
+            existing,
+        });
+    }
+    launch.markets = Some(markets);
+
+    launch.fin = Some(Fin {
+        contract_address: Some(plan.fin_address),
+    });
+
+    launch.bow = Some(Bow {
+        contract_address: Some(plan.bow_address),
+    });
+    launch.liquidity = Some(plan.liquidity);
+
+    launch.save(deps.storage)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "post_execute")
+        .add_attribute("idx", idx)
+        .add_submessages(plan.messages))
+}
+
+/// Ensures the sale and orca parameters of a scheduled sale are accepted by Pilot and Orca
+/// The sale has to open in the future and close after the Orca waiting period, the premium of the
+/// highest slot has to stay below 100%
+/// The opens, closes, price, max slot and premium bounds are Keiko's own, from the `sale_bounds` of
+/// its Pilot config, only the sale fee is read from the config of the Pilot contract
+fn validate_pilot_sale(
+    bounds: &SaleBounds,
+    now: &Timestamp,
+    sale: &CreateSale,
+    orca: &CreateOrca,
+) -> Result<(), ContractError> {
+    ensure!(!sale.price.is_zero(), ContractError::InvalidSalePrice {});
+    ensure!(
+        sale.price >= bounds.min_price && sale.price <= bounds.max_price.unwrap_or(Decimal::MAX),
+        ContractError::SaleParamOutOfBounds("price".to_string(), sale.price.to_string())
+    );
+
+    ensure!(
+        sale.opens > *now,
+        ContractError::SaleOpensInPast(sale.opens.seconds().to_string())
+    );
+    let delay = sale.opens.seconds() - now.seconds();
+    ensure!(
+        delay >= bounds.min_opens_delay && delay <= bounds.max_opens_delay.unwrap_or(u64::MAX),
+        ContractError::SaleParamOutOfBounds("opens".to_string(), sale.opens.seconds().to_string())
+    );
+
+    ensure!(
+        sale.closes > sale.opens,
+        ContractError::SaleClosesBeforeOpens(
+            sale.closes.seconds().to_string(),
+            sale.opens.seconds().to_string()
+        )
+    );
+    let duration = sale.closes.seconds() - sale.opens.seconds();
+    ensure!(
+        duration >= bounds.min_duration && duration <= bounds.max_duration.unwrap_or(u64::MAX),
+        ContractError::SaleParamOutOfBounds(
+            "closes".to_string(),
+            sale.closes.seconds().to_string()
+        )
+    );
+    ensure!(
+        duration > orca.waiting_period,
+        ContractError::WaitingPeriodTooLong(orca.waiting_period.to_string())
+    );
+
+    ensure!(orca.max_slot > 0, ContractError::InvalidMaxSlot {});
+    ensure!(
+        orca.max_slot <= bounds.max_slot.unwrap_or(u8::MAX),
+        ContractError::SaleParamOutOfBounds("max_slot".to_string(), orca.max_slot.to_string())
+    );
+
+    let max_premium = orca
+        .premium_rate_per_slot
+        .checked_mul(Decimal::from_ratio(orca.max_slot, 1u8))
+        .unwrap_or(Decimal::MAX);
+    ensure!(
+        !orca.premium_rate_per_slot.is_zero() && max_premium < Decimal::one(),
+        ContractError::InvalidPremiumRate(orca.premium_rate_per_slot.to_string())
+    );
+    ensure!(
+        max_premium <= bounds.max_premium.unwrap_or(Decimal::one()),
+        ContractError::SaleParamOutOfBounds(
+            "premium_rate_per_slot".to_string(),
+            orca.premium_rate_per_slot.to_string()
+        )
+    );
+    Ok(())
+}
+
+/// Ensures the FIN fees and BOW curve chosen for a launch are within the bounds of the config and
+/// that its liquidity pricing is valid
+fn validate_market_params(
+    api: &dyn Api,
+    config: &Config,
+    market: &MarketParams,
+) -> Result<(), ContractError> {
+    let fin = &config.fin.fee_bounds;
+    if let Some(fee_maker) = market.fee_maker {
+        ensure!(
+            fee_maker >= fin.min_fee_maker
+                && fee_maker <= fin.max_fee_maker.unwrap_or(Decimal256::one()),
+            ContractError::MarketParamOutOfBounds("fee_maker".to_string(), fee_maker.to_string())
+        );
+    }
+    if let Some(fee_taker) = market.fee_taker {
+        ensure!(
+            fee_taker >= fin.min_fee_taker
+                && fee_taker <= fin.max_fee_taker.unwrap_or(Decimal256::one()),
+            ContractError::MarketParamOutOfBounds("fee_taker".to_string(), fee_taker.to_string())
+        );
+    }
+
+    let bow = &config.bow.bounds;
+    if let Some(intervals) = market.bow_intervals.clone() {
+        ensure!(
+            !intervals.is_empty()
+                && intervals.len() <= bow.max_intervals.map_or(usize::MAX, |m| m as usize),
+            ContractError::MarketParamOutOfBounds(
+                "bow_intervals".to_string(),
+                intervals.len().to_string()
+            )
+        );
+        for interval in intervals {
+            ensure!(
+                interval >= bow.min_interval
+                    && interval <= bow.max_interval.unwrap_or(Decimal::one()),
+                ContractError::MarketParamOutOfBounds(
+                    "bow_intervals".to_string(),
+                    interval.to_string()
+                )
+            );
+        }
+    }
+    if let Some(fee) = market.bow_fee {
+        ensure!(
+            fee >= bow.min_fee && fee <= bow.max_fee.unwrap_or(Decimal::one()),
+            ContractError::MarketParamOutOfBounds("bow_fee".to_string(), fee.to_string())
+        );
+    }
+    if let Some(amp) = market.bow_amp {
+        ensure!(
+            amp >= bow.min_amp && amp <= bow.max_amp.unwrap_or(Decimal::MAX),
+            ContractError::MarketParamOutOfBounds("bow_amp".to_string(), amp.to_string())
+        );
+    }
+    if let Some(LiquidityPricing::Floor { price }) = market.liquidity_pricing {
+        ensure!(
+            !price.is_zero(),
+            ContractError::MarketParamOutOfBounds(
+                "liquidity_pricing".to_string(),
+                price.to_string()
+            )
+        );
+    }
+    if let Some(LiquidityLeftover::Treasury { address }) = market.leftover.clone() {
+        api.addr_validate(address.as_str())?;
+    }
+    Ok(())
+}
+
+/// Ensures the funds sent cover the BOW creation fee and, for non managed tokens, every non sale
+/// allocation of the launch
+fn ensure_post_launch_deposit(
+    launch: &Launch,
+    bow_fee: Coin,
+    funds: Vec<Coin>,
+) -> Result<(), ContractError> {
+    if !launch.clone().token.unwrap().is_managed {
+        let mut non_managed_amount = Uint128::zero();
+        for category in launch.clone().tokenomics.unwrap().categories {
+            if category.category_type != CategoryTypes::Sale {
+                for recipient in category.recipients {
+                    non_managed_amount = non_managed_amount.add(recipient.amount);
+                }
+            }
+        }
+        let mut balances = NativeBalance(funds);
+        if !bow_fee.amount.is_zero() {
+            balances = balances.sub(bow_fee)?;
+        }
+        let balances = balances.into_vec();
+        ensure!(balances.len() == 1, ContractError::InvalidFunds {});
+        ensure!(
+            non_managed_amount == balances[0].amount,
+            ContractError::InvalidDeposit(
+                non_managed_amount.to_string(),
+                balances[0].amount.to_string()
+            )
+        );
+    } else if bow_fee.amount.is_zero() {
+        ensure!(funds.is_empty(), ContractError::InvalidFunds {});
+    } else {
+        ensure!(funds.len() == 1, ContractError::InvalidFunds {});
+        ensure!(
+            funds[0] == bow_fee,
+            ContractError::InvalidDeposit(bow_fee.amount.to_string(), funds[0].amount.to_string())
+        );
+    }
+    Ok(())
+}
+
+/// Records the result of an executed pilot sale, the Pilot deposit of every sale but the first of
+/// the round is returned to the owner
+fn record_sale_result(
+    deps: DepsMut<KujiraQuery>,
+    config: &Config,
+    launch: &mut Launch,
+    sale_idx: usize,
+) -> Result<Vec<CosmosMsg<KujiraMsg>>, ContractError> {
+    let mut pilot = launch.pilots[sale_idx].clone();
+    let sale: kujira_pilot::SaleResponse = deps.querier.query_wasm_smart(
+        config.pilot.pilot_contract.clone(),
+        &kujira_pilot::QueryMsg::Sale {
+            idx: pilot.idx.unwrap(),
+        },
+    )?;
+
+    pilot.result = if let Status::Executed {
+        at,
+        raise_total,
+        raise_fee,
+        raise_amount,
+    } = sale.status
+    {
+        Some(SaleResult {
+            at,
+            raise_total,
+            raise_fee,
+            raise_amount,
+        })
+    } else {
+        return Err(ContractError::SaleNotExecuted {});
+    };
+    launch.pilots[sale_idx] = pilot;
+    launch.save(deps.storage)?;
+
+    let mut messages = vec![];
+    if !launch.is_round_lead(sale_idx) {
+        let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
+            config.pilot.pilot_contract.clone(),
+            &kujira_pilot::QueryMsg::Config {},
+        )?;
+        if !pilot_config.deposit.amount.is_zero() {
+            messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: launch.owner.to_string(),
+                amount: vec![pilot_config.deposit],
+            }));
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Orca bids of the bidder left on the sales of the launch, the unclaimed ones Keiko placed through
+/// AllowlistBid and the ones the bidder placed directly
+fn bidder_positions(
+    deps: Deps<KujiraQuery>,
+    config: &Config,
+    launch: &Launch,
+    bidder: &Addr,
+) -> StdResult<Vec<BidderPosition>> {
+    let mut positions = vec![];
+    let token = match launch.token.as_ref() {
+        Some(token) => token,
+        None => return Ok(positions),
+    };
+    let keiko_bids = ALLOWLIST_BIDS
+        .may_load(deps.storage, (launch.idx.u128(), bidder))?
+        .unwrap_or_default();
+
+    for (sale, pilot) in launch.pilots.iter().enumerate() {
+        let orca_address = match pilot.orca_address.as_ref() {
+            Some(orca_address) => orca_address,
+            None => continue,
+        };
+        let live = !sale_closed(&deps.querier, config, pilot)?;
+
+        let mut bids = vec![];
+        for bid in keiko_bids
+            .iter()
+            .filter(|b| b.sale as usize == sale && !b.claimed)
+        {
+            let response: kujira_orca::BidResponse = deps.querier.query_wasm_smart(
+                orca_address.clone(),
+                &kujira_orca::QueryMsg::Bid {
+                    bid_idx: bid.bid_idx,
+                },
+            )?;
+            bids.push((response, true));
+        }
+        let mut start_after = None;
+        loop {
+            let page: kujira_orca::BidsResponse = deps.querier.query_wasm_smart(
+                orca_address.clone(),
+                &kujira_orca::QueryMsg::BidsByUser {
+                    bidder: bidder.clone(),
+                    start_after,
+                    limit: None,
+                },
+            )?;
+            if page.bids.is_empty() {
+                break;
+            }
+            start_after = page.bids.last().map(|b| b.idx);
+            bids.extend(page.bids.into_iter().map(|b| (b, false)));
+        }
+
+        for (bid, keiko) in bids {
+            if bid.amount.is_zero() && bid.pending_liquidated_collateral.is_zero() {
+                continue;
+            }
+            positions.push(BidderPosition {
+                idx: launch.idx,
+                sale: sale as u8,
+                orca_address: orca_address.clone(),
+                bid_idx: bid.idx,
+                premium_slot: bid.premium_slot,
+                retractable: coin(bid.amount.u128(), pilot.orca.bid_denom.to_string()),
+                claimable: coin(
+                    bid.pending_liquidated_collateral.u128(),
+                    token.denom.to_string(),
+                ),
+                keiko,
+                live,
+            });
+        }
+    }
+    Ok(positions)
+}
+
+/// Whether the Pilot sale has been executed or retracted, the bids on its Orca queue are then done
+/// with
+fn sale_closed(
+    querier: &QuerierWrapper<KujiraQuery>,
+    config: &Config,
+    pilot: &Pilot,
+) -> StdResult<bool> {
+    if pilot.result.is_some() {
+        return Ok(true);
+    }
+    let idx = match pilot.idx {
+        Some(idx) => idx,
+        None => return Ok(false),
+    };
+    let sale: kujira_pilot::SaleResponse = querier.query_wasm_smart(
+        config.pilot.pilot_contract.clone(),
+        &kujira_pilot::QueryMsg::Sale { idx },
+    )?;
+    Ok(matches!(
+        sale.status,
+        Status::Executed { .. } | Status::Retracted { .. }
+    ))
+}
+
+/// Every bid pool of an Orca queue, the pages are requested after the last premium slot fetched,
+/// which is derived from the premium rate of the last pool as slots without bids can be skipped
+fn query_bid_pools(
+    querier: &QuerierWrapper<KujiraQuery>,
+    orca_address: &Addr,
+    orca: &CreateOrca,
+) -> StdResult<BidPoolsResponse> {
+    let mut bid_pools = vec![];
+    let mut start_after = None;
+    loop {
+        let page: BidPoolsResponse = querier.query_wasm_smart(
+            orca_address.clone(),
+            &kujira_orca::QueryMsg::BidPools {
+                start_after,
+                limit: Some(100),
+            },
+        )?;
+        let fetched = page.bid_pools.len();
+        let last_slot = page
+            .bid_pools
+            .last()
+            .and_then(|pool| pricing::premium_slot(pool.premium_rate, orca.premium_rate_per_slot));
+        bid_pools.extend(page.bid_pools);
+        if fetched < 100 || last_slot.is_none() || last_slot == Some(orca.max_slot) {
+            return Ok(BidPoolsResponse { bid_pools });
+        }
+        start_after = last_slot;
+    }
+}
+
+/// Executes the pilot sales of a round, the reply of the last sale is of the given type so it runs
+/// once every sale of the round has been executed, the bid pools are snapshotted before the
+/// execution
+fn execute_round(
+    deps: DepsMut<KujiraQuery>,
+    config: &Config,
+    launch: &mut Launch,
+    sales: &[usize],
+    last: ReplyTypes,
+) -> Result<Vec<SubMsg<KujiraMsg>>, ContractError> {
+    let mut messages = vec![];
+    for sale_idx in sales.iter().copied() {
+        let mut pilot = launch.pilots[sale_idx].clone();
+        let reply = ReplyInfo {
+            reply_type: if Some(sale_idx) == sales.last().copied() {
+                last.clone()
+            } else {
+                ReplyTypes::Execute
+            },
+            idx: launch.idx,
+            sale: sale_idx as u8,
+        };
+        messages.push(SubMsg::reply_on_success(
+            CosmosMsg::Wasm(wasm_execute(
+                config.clone().pilot.pilot_contract,
+                &kujira_pilot::ExecuteMsg::Execute {
+                    idx: pilot.idx.unwrap(),
+                },
+                vec![],
+            )?),
+            reply.id()?,
+        ));
+        REPLIES.save(deps.storage, reply.id()?, &reply)?;
+
+        let sale: kujira_pilot::SaleResponse = deps.querier.query_wasm_smart(
+            config.pilot.pilot_contract.clone(),
+            &kujira_pilot::QueryMsg::Sale {
+                idx: pilot.idx.unwrap(),
+            },
+        )?;
+
+        if let Ok(bid_pools) = query_bid_pools(&deps.querier, &sale.orca_address, &pilot.orca) {
+            BID_POOLS.save(
+                deps.storage,
+                (launch.idx.u128(), sale_idx as u8),
+                &bid_pools,
+            )?;
+        }
+
+        pilot.orca_address = Some(sale.orca_address);
+        launch.pilots[sale_idx] = pilot;
+    }
+    launch.save(deps.storage)?;
+
+    Ok(messages)
+}
+
+/// Starts the pilot sales of a round by creating them on the pilot contract and sets the status to
+/// sale live, the sender is recorded in the launch history
+fn start_round(
+    deps: DepsMut<KujiraQuery>,
+    env: &Env,
+    info: &MessageInfo,
+    config: &Config,
+    mut launch: Launch,
+    round: Option<u8>,
+) -> Result<Response<KujiraMsg>, ContractError> {
+    let idx = launch.idx;
+    launch.transition(deps.storage, env, &info.sender, LaunchAction::PilotStart)?;
+
+    let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
+        config.pilot.pilot_contract.clone(),
+        &kujira_pilot::QueryMsg::Config {},
+    )?;
+
+    // rounds are started in order, with every sale of the round scheduled
+    let next_round = launch.started_round().map_or(0, |r| r + 1);
+    let round = round.unwrap_or(next_round);
+    ensure!(
+        round == next_round,
+        ContractError::InvalidRound(round.to_string())
+    );
+    let allocations = launch.sale_allocations();
+    let sales: Vec<usize> = allocations
+        .iter()
+        .enumerate()
+        .filter(|(_, (r, _))| *r == round)
+        .map(|(sale_idx, _)| sale_idx)
+        .collect();
+    ensure!(
+        !sales.is_empty() && sales.iter().all(|s| *s < launch.pilots.len()),
+        ContractError::InvalidRound(round.to_string())
+    );
+
+    ensure!(
+        launch.token.clone().is_some()
+            && launch.tokenomics.clone().is_some()
+            && sales.iter().all(|s| launch.pilots[*s].idx.is_none()
+                && launch.pilots[*s].sale.opens.seconds() <= env.block.time.seconds()),
+        ContractError::Unauthorized {}
+    );
+
+    let mut messages = vec![];
+    let denom = launch.clone().token.unwrap().denom;
+    let round_amount = sales
+        .iter()
+        .fold(Uint128::zero(), |acc, s| acc + allocations[*s].1);
+
+    // the launch deposit covers the Pilot deposit of the first sale
+    let extra_deposit = coin(
+        pilot_config.deposit.amount.u128() * (sales.len() as u128 - 1),
+        pilot_config.deposit.denom.clone(),
+    );
+
+    if launch.clone().token.unwrap().is_managed {
+        messages.push(SubMsg::new(CosmosMsg::Custom(KujiraMsg::Denom(
+            DenomMsg::Mint {
+                denom: denom.clone(),
+                amount: round_amount,
+                recipient: env.contract.address.clone(),
+            },
+        ))));
+
+        if !extra_deposit.amount.is_zero() {
+            ensure!(
+                info.funds == vec![extra_deposit.clone()],
+                ContractError::InvalidFunds {}
+            );
+        } else {
+            ensure!(info.funds.is_empty(), ContractError::InvalidFunds {});
+        }
+    } else {
+        let mut balances = NativeBalance(info.funds.clone());
+        if !extra_deposit.amount.is_zero() {
+            balances = balances
+                .sub(extra_deposit.clone())
+                .map_err(|_| ContractError::InvalidFunds {})?;
+        }
+        balances.normalize();
+        let balances = balances.into_vec();
+
+        ensure!(
+            balances.len() == 1,
+            ContractError::Payment(PaymentError::MultipleDenoms {})
+        );
+
+        ensure!(
+            balances[0].denom == denom.to_string() && balances[0].amount == round_amount,
+            ContractError::InvalidFunds {}
+        );
+    };
+
+    for sale_idx in sales {
+        let pilot = launch.pilots[sale_idx].clone();
+        let bid_denom = config
+            .pilot
+            .allowed_bid_denoms
+            .iter()
+            .find(|d| d.denom == pilot.orca.bid_denom.clone());
+
+        let mut sale = pilot.sale.clone();
+        sale.price = pricing::base_price(
+            sale.price,
+            pricing::decimal_delta(
+                launch.clone().token.unwrap().decimals,
+                bid_denom.unwrap().decimals,
+            ),
+        );
+
+        let reply = ReplyInfo {
+            reply_type: ReplyTypes::Create,
+            idx,
+            sale: sale_idx as u8,
+        };
+        messages.push(SubMsg::reply_on_success(
+            CosmosMsg::Wasm(wasm_execute(
+                config.pilot.pilot_contract.clone(),
+                &kujira_pilot::ExecuteMsg::Create {
+                    sale,
+                    orca: pilot.orca,
+                },
+                vec![
+                    pilot_config.deposit.clone(),
+                    coin(allocations[sale_idx].1.u128(), denom.to_string()),
+                ],
+            )?),
+            reply.id()?,
+        ));
+        REPLIES.save(deps.storage, reply.id()?, &reply)?;
+    }
+
+    launch.save(deps.storage)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "pilot_start")
+        .add_attribute("idx", idx)
+        .add_attribute("round", round.to_string())
+        .add_submessages(messages))
+}
+
+/// Launches of the statuses in descending idx order, read lazily from the status index so a page
+/// only loads the launches up to its last one
+fn launches_by_status<'a>(
+    storage: &'a dyn Storage,
+    statuses: &[LaunchStatus],
+    start_after: Option<Uint128>,
+) -> impl Iterator<Item = StdResult<(u128, Launch)>> + 'a {
+    let mut ranges: Vec<_> = statuses
+        .iter()
+        .map(|status| {
+            launch()
+                .idx
+                .status
+                .prefix(status.to_string())
+                .range(
+                    storage,
+                    None,
+                    start_after.map(|x| Bound::exclusive(x.u128())),
+                    Order::Descending,
+                )
+                .peekable()
+        })
+        .collect();
+    std::iter::from_fn(move || {
+        // errors are returned first, then the highest idx of the statuses
+        let next = ranges
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, range)| {
+                range
+                    .peek()
+                    .map(|item| (i, item.as_ref().map_or(u128::MAX, |(idx, _)| *idx)))
+            })
+            .max_by_key(|(_, idx)| *idx)
+            .map(|(i, _)| i)?;
+        ranges[next].next()
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<KujiraQuery>, _: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps<KujiraQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::Launch { idx } => {
@@ -1226,6 +2111,11 @@ pub fn query(deps: Deps<KujiraQuery>, _: Env, msg: QueryMsg) -> StdResult<Binary
             to_json_binary(&launch)
         }
         QueryMsg::LaunchByPilotIdx { idx } => {
+            // Sales of every round are recorded when they are created, the index only holds the
+            // first round of launches created before rounds
+            if let Some((launch_idx, _)) = PILOT_SALES.may_load(deps.storage, idx.u128())? {
+                return to_json_binary(&Launch::load(deps.storage, Uint128::from(launch_idx))?);
+            }
             let launches = launch()
                 .idx
                 .pilot_idx
@@ -1289,37 +2179,486 @@ pub fn query(deps: Deps<KujiraQuery>, _: Env, msg: QueryMsg) -> StdResult<Binary
                 .collect::<StdResult<Vec<Launch>>>()?;
             to_json_binary(&launches)
         }
+        QueryMsg::LaunchHistory {
+            idx,
+            start_after,
+            limit,
+        } => {
+            let history = LAUNCH_HISTORY
+                .prefix(idx.u128())
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.unwrap_or(10) as usize)
+                .map(|x| x.map(|y| y.1))
+                .collect::<StdResult<Vec<LaunchHistoryEntry>>>()?;
+            to_json_binary(&history)
+        }
+        QueryMsg::SimulatePostLaunch { idx } => {
+            let config = CONFIG.load(deps.storage)?;
+            let launch = Launch::load(deps.storage, idx)?;
+            ensure!(
+                next_status(&launch.status, &LaunchAction::PostLaunch).is_some(),
+                StdError::generic_err(
+                    ContractError::InvalidTransition(
+                        launch.status.to_string(),
+                        LaunchAction::PostLaunch.to_string()
+                    )
+                    .to_string()
+                )
+            );
+            let plan = post_launch::plan(deps, &env, &config, &launch)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            to_json_binary(&SimulatePostLaunchResponse {
+                average_price: plan.average_price,
+                price_precision_decimals: plan.price_precision_decimals,
+                lp_stable_amount: plan.lp_stable_amount,
+                beneficiary_funds: plan.beneficiary_funds,
+                fin_address: plan.fin_address,
+                bow_address: plan.bow_address,
+                markets: plan.markets,
+                liquidity: plan.liquidity,
+                required_deposit: plan.required_deposit,
+                messages: plan.messages.into_iter().map(|m| m.msg).collect(),
+            })
+        }
+        QueryMsg::EstimateRaise {
+            idx,
+            round,
+            recipient,
+            price,
+            orca,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            let launch = Launch::load(deps.storage, idx)?;
+            let round = round.unwrap_or(0);
+            let (_, amount) = launch
+                .sale_index(round, recipient)
+                .map(|sale_idx| launch.sale_allocations()[sale_idx])
+                .ok_or_else(|| {
+                    StdError::generic_err(
+                        ContractError::InvalidRound(round.to_string()).to_string(),
+                    )
+                })?;
+            let token = launch
+                .token
+                .ok_or_else(|| StdError::generic_err("Launch has no token"))?;
+            let bid_denom = config
+                .pilot
+                .allowed_bid_denoms
+                .iter()
+                .find(|d| d.denom == orca.bid_denom)
+                .ok_or_else(|| {
+                    StdError::generic_err(ContractError::InvalidBidDenom {}.to_string())
+                })?;
+            let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
+                config.pilot.pilot_contract,
+                &kujira_pilot::QueryMsg::Config {},
+            )?;
+            let estimate = raise::estimate(
+                amount,
+                price,
+                token.decimals,
+                bid_denom.decimals,
+                &orca,
+                pilot_config.sale_fee,
+            )
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+            to_json_binary(&estimate)
+        }
+        QueryMsg::PredictedAddresses { idx } => {
+            let config = CONFIG.load(deps.storage)?;
+            let launch = Launch::load(deps.storage, idx)?;
+            let (fin, bow) = post_launch::predicted_addresses(deps, &env, &config, &launch)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            to_json_binary(&PredictedAddressesResponse { fin, bow })
+        }
+        QueryMsg::AllowlistEligibility {
+            idx,
+            address,
+            denom,
+            cap,
+            proof,
+        } => {
+            let launch = Launch::load(deps.storage, idx)?;
+            let eligible = launch.allowlist.map_or(false, |allowlist| {
+                allowlist::verify(&allowlist, &address, cap, &proof).is_ok()
+            });
+            let bid = ALLOWLIST_BIDS
+                .may_load(deps.storage, (idx.u128(), &address))?
+                .unwrap_or_default()
+                .iter()
+                .filter(|b| b.amount.denom == denom.to_string())
+                .fold(Uint128::zero(), |acc, b| acc + b.amount.amount);
+            to_json_binary(&AllowlistEligibilityResponse {
+                eligible,
+                bid,
+                remaining: cap.map(|cap| cap.saturating_sub(bid)),
+            })
+        }
+        QueryMsg::LaunchesAwaitingPostLaunch { start_after, limit } => {
+            let launches = launch()
+                .idx
+                .status
+                .prefix(LaunchStatus::SaleExecuted.to_string())
+                .range(
+                    deps.storage,
+                    None,
+                    start_after.map(|x| Bound::exclusive(x.u128())),
+                    Order::Descending,
+                )
+                .filter(|x| {
+                    x.as_ref().map_or(true, |(_, l)| {
+                        l.token.as_ref().map_or(false, |t| t.is_managed) && l.all_rounds_started()
+                    })
+                })
+                .take(limit.unwrap_or(10) as usize)
+                .map(|x| x.map(|y| y.1))
+                .collect::<StdResult<Vec<Launch>>>()?;
+            to_json_binary(&launches)
+        }
+        QueryMsg::LaunchesReadyToStart { start_after, limit } => {
+            let launches = launches_by_status(
+                deps.storage,
+                &[LaunchStatus::Scheduled, LaunchStatus::SaleExecuted],
+                start_after,
+            )
+            .filter(|x| {
+                x.as_ref()
+                    .map_or(true, |(_, l)| l.keeper_round(&env.block.time).is_some())
+            })
+            .take(limit.unwrap_or(10) as usize)
+            .map(|x| x.map(|y| y.1))
+            .collect::<StdResult<Vec<Launch>>>()?;
+            to_json_binary(&launches)
+        }
+        QueryMsg::AllowlistBids { idx, bidder } => to_json_binary(
+            &ALLOWLIST_BIDS
+                .may_load(deps.storage, (idx.u128(), &bidder))?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::SaleAnalytics { idx } => {
+            let config = CONFIG.load(deps.storage)?;
+            let launch = Launch::load(deps.storage, idx)?;
+            let allocations = launch.sale_allocations();
+            let mut analytics = vec![];
+            for (sale, pilot) in launch.pilots.iter().enumerate() {
+                // only the sales that have been executed have their bid pools snapshotted
+                let (bid_pools, token) =
+                    match (launch.bid_pools(deps.storage, sale)?, launch.token.as_ref()) {
+                        (Some(bid_pools), Some(token)) => (bid_pools, token),
+                        _ => continue,
+                    };
+                let (round, amount) = allocations[sale];
+                let bid_decimals = config
+                    .pilot
+                    .allowed_bid_denoms
+                    .iter()
+                    .find(|d| d.denom == pilot.orca.bid_denom)
+                    .map_or(token.decimals, |d| d.decimals);
+                let price = pricing::base_price(
+                    pilot.sale.price,
+                    pricing::decimal_delta(token.decimals, bid_decimals),
+                );
+                let fills = pricing::slot_fills(price, &bid_pools, amount);
+                let clearing_premium =
+                    pricing::clearing_slot(&fills, amount).map(|slot| slot.premium_rate);
+                let slots: Vec<SlotAnalytics> = fills
+                    .iter()
+                    .enumerate()
+                    .map(|(position, fill)| SlotAnalytics {
+                        // the snapshot may leave out slots, the premium gives the slot back
+                        slot: pricing::premium_slot(
+                            fill.premium_rate,
+                            pilot.orca.premium_rate_per_slot,
+                        )
+                        .unwrap_or(position as u8),
+                        premium_rate: fill.premium_rate,
+                        total_bid_amount: fill.bid_amount,
+                        filled_amount: fill.filled_amount,
+                        unfilled_amount: fill.bid_amount - fill.filled_amount,
+                    })
+                    .collect();
+                let total_bid_amount = slots
+                    .iter()
+                    .fold(Uint128::zero(), |acc, s| acc + s.total_bid_amount);
+                let filled_amount = slots
+                    .iter()
+                    .fold(Uint128::zero(), |acc, s| acc + s.filled_amount);
+                analytics.push(SaleAnalytics {
+                    sale: sale as u8,
+                    round,
+                    bid_denom: pilot.orca.bid_denom.clone(),
+                    amount,
+                    total_bid_amount,
+                    filled_amount,
+                    unfilled_amount: total_bid_amount - filled_amount,
+                    clearing_premium,
+                    clearing_price: clearing_premium
+                        .map(|premium| pilot.sale.price * (Decimal::one() - premium)),
+                    slots,
+                });
+            }
+            to_json_binary(&analytics)
+        }
+        QueryMsg::BidderPositions {
+            bidder,
+            start_after,
+            limit,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            let mut positions = vec![];
+            for item in launch()
+                .range(
+                    deps.storage,
+                    None,
+                    start_after.map(|x| Bound::exclusive(x.u128())),
+                    Order::Descending,
+                )
+                .take(limit.unwrap_or(10) as usize)
+            {
+                let (_, launch) = item?;
+                positions.extend(bidder_positions(deps, &config, &launch, &bidder)?);
+            }
+            to_json_binary(&positions)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(
-    deps: DepsMut<KujiraQuery>,
-    _env: Env,
+    mut deps: DepsMut<KujiraQuery>,
+    env: Env,
     msg: Reply,
 ) -> Result<Response<KujiraMsg>, ContractError> {
-    let storage = deps.storage;
-    let reply_info = REPLY.load(storage)?;
+    let reply_info = REPLIES
+        .may_load(deps.storage, msg.id)?
+        .ok_or_else(|| ContractError::UnknownReplyId(msg.id.to_string()))?;
+    REPLIES.remove(deps.storage, msg.id);
+
     let data = msg.result.into_result().map_err(StdError::generic_err)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut launch = Launch::load(deps.storage, reply_info.idx)?;
+
+    match reply_info.reply_type {
+        ReplyTypes::Create => {
+            // Stores the idx of the sale created on the pilot contract
+            // and its Orca queue, which takes bids while the sale is live
+            let sale_idx = parse_pilot_sale_idx(&data, &config.pilot.pilot_contract)?;
+            let sale: kujira_pilot::SaleResponse = deps.querier.query_wasm_smart(
+                config.pilot.pilot_contract.clone(),
+                &kujira_pilot::QueryMsg::Sale { idx: sale_idx },
+            )?;
+
+            launch.pilots[reply_info.sale as usize].idx = Some(sale_idx);
+            launch.pilots[reply_info.sale as usize].orca_address = Some(sale.orca_address);
+            launch.save(deps.storage)?;
+            PILOT_SALES.save(
+                deps.storage,
+                sale_idx.u128(),
+                &(launch.idx.u128(), reply_info.sale),
+            )?;
 
-    match msg.id {
-        1 => {
-            let mut launch = Launch::load(storage, reply_info.idx)?;
+            Ok(Response::new().add_attribute("create_reply_response_ok", msg.id.to_string()))
+        }
+        ReplyTypes::Execute => {
+            // Records the result of the executed pilot sale
+            // The Pilot deposit of every sale but the first of the round is returned to the owner
+            let messages =
+                record_sale_result(deps, &config, &mut launch, reply_info.sale as usize)?;
+
+            Ok(Response::new()
+                .add_attribute("execute_reply_response_ok", msg.id.to_string())
+                .add_messages(messages))
+        }
+        ReplyTypes::Settle => {
+            // Records the result of the last sale of the round and performs the post launch actions
+            // now that every sale of the launch has been executed
+            let mut messages = record_sale_result(
+                deps.branch(),
+                &config,
+                &mut launch,
+                reply_info.sale as usize,
+            )?;
+            let owner = launch.owner.clone();
+            launch.transition(deps.storage, &env, &owner, LaunchAction::PostLaunch)?;
 
-            let attribute = data
+            let prefunded = std::mem::take(&mut launch.prefunded);
+            if !prefunded.is_empty() {
+                messages.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: owner.to_string(),
+                    amount: prefunded,
+                }));
+            }
+
+            Ok(deploy_liquidity(deps, &env, &config, launch)?
+                .add_attribute("settle_reply_response_ok", msg.id.to_string())
+                .add_messages(messages))
+        }
+        ReplyTypes::PostExecute => {
+            // Ensures the FIN contract was instantiated at the address the launch was set up with
+            let instantiated = data
                 .events
                 .iter()
+                .filter(|e| e.ty == "instantiate")
                 .flat_map(|e| e.attributes.clone())
-                .find(|a| a.key == "sale")
-                .unwrap();
+                .find(|a| a.key == "_contract_address")
+                .map(|a| a.value);
+            let expected = launch
+                .markets
+                .and_then(|markets| markets.get(reply_info.sale as usize).cloned())
+                .map(|market| market.fin.to_string());
 
-            let mut pilot = launch.pilot.unwrap();
-            pilot.idx = Some(Uint128::from_str(&attribute.value)?);
-            launch.pilot = Some(pilot);
-            launch.save(storage)?;
+            ensure!(
+                instantiated.is_some() && instantiated == expected,
+                ContractError::InstantiatedAddressMismatch(
+                    expected.unwrap_or_default(),
+                    instantiated.unwrap_or_default()
+                )
+            );
 
-            Ok(Response::new().add_attribute("create_reply_response_ok", msg.id.to_string()))
+            Ok(Response::new().add_attribute("post_execute_reply_response_ok", msg.id.to_string()))
+        }
+        ReplyTypes::Retract => {
+            // Returns the sale funds to the owner once the sale is retracted
+            // When the whole launch is retracted the deposit and the prefunded funds are returned as
+            // well and the denom admin is changed back to the owner if the token is managed, once for
+            // the first sale of the round, the other sales of the round always return the Pilot
+            // deposit of the owner
+            // When the launch continues the minted sale tokens of a managed token are burned
+            let mut messages = vec![];
+            let sale_idx = reply_info.sale as usize;
+            let lead = launch.is_round_lead(sale_idx);
+            let continues = launch.status == LaunchStatus::SaleExecuted;
+            let token = launch.clone().token.unwrap();
+
+            if lead && !continues && token.is_managed {
+                messages.push(CosmosMsg::Custom(KujiraMsg::Denom(DenomMsg::ChangeAdmin {
+                    denom: token.denom.clone(),
+                    address: launch.owner.clone(),
+                })));
+            }
+
+            let (_, sale_amount) = launch.sale_allocations()[sale_idx];
+
+            let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
+                config.pilot.pilot_contract.clone(),
+                &kujira_pilot::QueryMsg::Config {},
+            )?;
+
+            let mut amount = NativeBalance::default();
+            if continues && token.is_managed {
+                messages.push(CosmosMsg::Custom(KujiraMsg::Denom(DenomMsg::Burn {
+                    denom: token.denom.clone(),
+                    amount: sale_amount,
+                })));
+            } else {
+                amount.add_assign(coin(sale_amount.u128(), token.denom.to_string()));
+            }
+            if !lead {
+                amount.add_assign(pilot_config.deposit);
+            } else if !continues {
+                amount.add_assign(pilot_config.deposit.clone());
+                amount = amount + NativeBalance(std::mem::take(&mut launch.prefunded));
+                launch.save(deps.storage)?;
+            }
+            amount.normalize();
+
+            if !amount.is_empty() {
+                messages.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: launch.owner.to_string(),
+                    amount: amount.into_vec(),
+                }));
+            }
+
+            Ok(Response::new()
+                .add_attribute("retract_reply_response_ok", msg.id.to_string())
+                .add_messages(messages))
         }
-        id => Err(ContractError::UnknownReplyId(id.to_string())),
+        ReplyTypes::Bid => {
+            // Records the bid placed on Orca for the allowlisted bidder
+            let (bidder, amount) = PENDING_BIDS.load(deps.storage, msg.id)?;
+            PENDING_BIDS.remove(deps.storage, msg.id);
+
+            let sale: kujira_pilot::SaleResponse = deps.querier.query_wasm_smart(
+                config.pilot.pilot_contract,
+                &kujira_pilot::QueryMsg::Sale {
+                    idx: launch.pilots[reply_info.sale as usize].idx.unwrap(),
+                },
+            )?;
+            let bid_idx = parse_orca_bid_idx(&data, &sale.orca_address)?;
+
+            ALLOWLIST_BIDS.update(
+                deps.storage,
+                (launch.idx.u128(), &bidder),
+                |bids| -> StdResult<_> {
+                    let mut bids = bids.unwrap_or_default();
+                    bids.push(AllowlistBid {
+                        sale: reply_info.sale,
+                        bid_idx,
+                        amount,
+                        claimed: false,
+                    });
+                    Ok(bids)
+                },
+            )?;
+
+            Ok(Response::new()
+                .add_attribute("bid_reply_response_ok", msg.id.to_string())
+                .add_attribute("bid_idx", bid_idx))
+        }
+    }
+}
+
+/// Reads the idx of the sale created on the pilot contract from the submessage response.
+/// The response data is preferred, it is the MsgExecuteContractResponse wrapping the data set by
+/// the pilot contract, otherwise the "sale" attribute of the wasm event emitted by the pilot
+/// contract is used. Attributes emitted by other contracts are ignored.
+pub fn parse_pilot_sale_idx(
+    response: &SubMsgResponse,
+    pilot_contract: &Addr,
+) -> Result<Uint128, ContractError> {
+    let data = response
+        .data
+        .as_ref()
+        .and_then(|data| parse_execute_response_data(data).ok())
+        .and_then(|execute| execute.data);
+    if let Some(idx) = data.and_then(|data| from_json::<Uint128>(&data).ok()) {
+        return Ok(idx);
     }
+
+    let attribute = contract_attribute(response, pilot_contract, "sale")
+        .ok_or(ContractError::PilotSaleIdxNotFound {})?;
+
+    Ok(Uint128::from_str(&attribute)?)
+}
+
+/// Reads the idx of the bid placed on the Orca queue from the "bid_idx" attribute of its wasm event
+pub fn parse_orca_bid_idx(
+    response: &SubMsgResponse,
+    orca_contract: &Addr,
+) -> Result<Uint128, ContractError> {
+    let attribute = contract_attribute(response, orca_contract, "bid_idx")
+        .ok_or(ContractError::OrcaBidIdxNotFound {})?;
+
+    Ok(Uint128::from_str(&attribute)?)
+}
+
+/// Value of the attribute emitted by the contract in its wasm events
+fn contract_attribute(response: &SubMsgResponse, contract: &Addr, key: &str) -> Option<String> {
+    response
+        .events
+        .iter()
+        .filter(|e| {
+            (e.ty == "wasm" || e.ty.starts_with("wasm-"))
+                && e.attributes
+                    .iter()
+                    .any(|a| a.key == "_contract_address" && a.value == contract.as_str())
+        })
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == key)
+        .map(|a| a.value.clone())
 }
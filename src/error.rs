@@ -40,8 +40,8 @@ pub enum ContractError {
     #[error("Sale already exists for denom {0}")]
     SaleAlreadyExistsDenom(String),
 
-    #[error("Sale already exists for owner {0}")]
-    SaleAlreadyExistsOwner(String),
+    #[error("Owner {0} already has the maximum of {1} active launches")]
+    MaxActiveLaunches(String, String),
 
     #[error("Either the symbol or the denom is required, but not both")]
     DynomOrSymbolRequired {},
@@ -49,6 +49,9 @@ pub enum ContractError {
     #[error("Launch in Status that does not allow this action")]
     InvalidStatus {},
 
+    #[error("Launch in Status {0} does not allow {1}")]
+    InvalidTransition(String, String),
+
     #[error("Invalid Funds")]
     InvalidFunds {},
 
@@ -64,12 +67,47 @@ pub enum ContractError {
     #[error("Minimum Raise Amount {0}, must be greater than {1}")]
     InvalidRaiseAmount(String, String),
 
-    #[error("Tokenomics requires one sale category")]
+    #[error("Sale price must be greater than zero")]
+    InvalidSalePrice {},
+
+    #[error("Sale opens at {0}, which is not in the future")]
+    SaleOpensInPast(String),
+
+    #[error("Sale closes at {0}, which is not after it opens at {1}")]
+    SaleClosesBeforeOpens(String, String),
+
+    #[error("Orca waiting period {0} is not shorter than the sale")]
+    WaitingPeriodTooLong(String),
+
+    #[error("Orca max slot must be greater than zero")]
+    InvalidMaxSlot {},
+
+    #[error(
+        "Orca premium rate per slot {0} must be greater than zero and below 100% at the max slot"
+    )]
+    InvalidPremiumRate(String),
+
+    #[error("Price precision of {0} decimal places is outside the bounds of the policy")]
+    InvalidPricePrecision(String),
+
+    #[error("Price precision policy {0} of {1} is invalid")]
+    InvalidPricePrecisionPolicy(String, String),
+
+    #[error("Market parameter {0} of {1} is outside the configured bounds")]
+    MarketParamOutOfBounds(String, String),
+
+    #[error("Sale parameter {0} of {1} is outside the configured bounds")]
+    SaleParamOutOfBounds(String, String),
+
+    #[error("Tokenomics requires at least one sale category")]
     OneSaleCategory {},
 
-    #[error("Tokenomics requires one sale category recipient")]
+    #[error("Tokenomics requires at least one recipient per sale category")]
     OneSaleCategoryRecipient {},
 
+    #[error("Tokenomics has {0} sale recipients, at most 256 Pilot sales fit in a launch")]
+    TooManySales(String),
+
     #[error("Tokenomics requires one liquidity category")]
     OneLiquidityCategory {},
 
@@ -102,4 +140,76 @@ pub enum ContractError {
 
     #[error("Unknown Reply Id {0}")]
     UnknownReplyId(String),
+
+    #[error("Liquidity targets are only allowed in the liquidity category: {0}")]
+    LiquidityTargetsNotAllowed(String),
+
+    #[error("Liquidity target shares must be positive and add up to one, got {0}")]
+    InvalidLiquidityShares(String),
+
+    #[error("LP vesting is only allowed in the liquidity category: {0}")]
+    LpVestingNotAllowed(String),
+
+    #[error("LP vesting shares must be positive and add up to one, got {0}")]
+    InvalidLpVestingShares(String),
+
+    #[error("LP vesting cliff {0} and duration {1} are outside the allowed bounds")]
+    LpVestingOutOfBounds(String, String),
+
+    #[error("Invalid Pilot round: {0}")]
+    InvalidRound(String),
+
+    #[error("FIN contract {0} does not trade the launch token against the bid denom")]
+    InvalidExistingFin(String),
+
+    #[error("Pilot sale idx not found in the create response")]
+    PilotSaleIdxNotFound {},
+
+    #[error("Pilot sale has not been executed")]
+    SaleNotExecuted {},
+
+    #[error("Contract expected at {0} but instantiated at {1}")]
+    InstantiatedAddressMismatch(String, String),
+
+    #[error("Launch has no allowlist")]
+    NoAllowlist {},
+
+    #[error("Invalid merkle hash: {0}")]
+    InvalidMerkleHash(String),
+
+    #[error("Address {0} is not on the allowlist")]
+    NotAllowlisted(String),
+
+    #[error("Allowlist cap {0} exceeded, the bids would add up to {1}")]
+    AllowlistCapExceeded(String, String),
+
+    #[error("Orca bid idx not found in the submit bid response")]
+    OrcaBidIdxNotFound {},
+
+    #[error("Launch idx {0} does not fit in a reply id")]
+    ReplyIdOverflow(String),
+
+    #[error("Launches stored by a previous version are still being migrated")]
+    MigrationPending {},
+
+    #[error("No launches are left to migrate")]
+    NoMigrationPending {},
+
+    #[error("Bidder {0} has no bids to claim")]
+    NothingToClaim(String),
+
+    #[error("Launch {0} has no round ready to be started by a keeper")]
+    NotReadyToStart(String),
+
+    #[error("Launch {0} is not awaiting PostLaunch")]
+    NotAwaitingPostLaunch(String),
+
+    #[error("Prefunded funds do not cover the BOW creation fee of {0}")]
+    InsufficientPrefund(String),
+
+    #[error("Round {0} has several sales and has to be started by the owner")]
+    MultiSaleKeeperRound(String),
+
+    #[error("Liquidity needs {0} of the raise but the sales raised {1}")]
+    LiquidityExceedsRaise(String, String),
 }
@@ -1,9 +1,13 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{ensure, Addr, Coin, Order, StdResult, Storage, Uint128};
+use cosmwasm_std::{ensure, Addr, Coin, Env, Order, StdResult, Storage, Timestamp, Uint128};
+use kujira_orca::BidPoolsResponse;
 
 use crate::{
-    msg::{Bow, Fin, LaunchStatus, Pilot, Token, Tokenomics},
-    state::launch,
+    msg::{
+        Allowlist, Bow, Fin, LaunchAction, LaunchHistoryEntry, LaunchStatus, LiquidityPosition,
+        Market, MarketParams, Pilot, Token, Tokenomics,
+    },
+    state::{launch, BID_POOLS, LAUNCH_HISTORY},
     ContractError,
 };
 
@@ -16,9 +20,29 @@ pub struct Launch {
     pub status: LaunchStatus,
     pub token: Option<Token>,
     pub tokenomics: Option<Tokenomics>,
-    pub pilot: Option<Pilot>,
+    #[serde(default)]
+    pub pilots: Vec<Pilot>,
     pub fin: Option<Fin>,
     pub bow: Option<Bow>,
+    pub liquidity: Option<Vec<LiquidityPosition>>,
+    pub existing_fin: Option<Addr>,
+    /// FIN pairs of the launch, one per bid denom raised, set by PostLaunch
+    pub markets: Option<Vec<Market>>,
+    /// only allowlisted addresses can bid through Keiko when set
+    pub allowlist: Option<Allowlist>,
+    /// keeper rewards paid out of the prefunded funds
+    #[serde(default)]
+    pub keeper_rewards: Uint128,
+    /// funds prefunding the BOW creation fee and keeper rewards so that anyone can start the rounds
+    /// and crank PostLaunch
+    #[serde(default)]
+    pub prefunded: Vec<Coin>,
+    /// decimal places of the FIN prices chosen by the owner instead of the policy of the config
+    #[serde(default)]
+    pub price_precision: Option<u8>,
+    /// FIN fees and BOW curve chosen by the owner at PilotSchedule
+    #[serde(default)]
+    pub market_params: Option<MarketParams>,
 }
 
 impl Launch {
@@ -43,9 +67,17 @@ impl Launch {
             status: LaunchStatus::Created,
             token: None,
             tokenomics: None,
-            pilot: None,
+            pilots: vec![],
             fin: None,
             bow: None,
+            liquidity: None,
+            existing_fin: None,
+            markets: None,
+            allowlist: None,
+            keeper_rewards: Uint128::zero(),
+            prefunded: vec![],
+            price_precision: None,
+            market_params: None,
         }
     }
 
@@ -57,8 +89,232 @@ impl Launch {
         launch().save(storage, self.idx.u128(), self)
     }
 
+    /// Orca bid pools of a sale snapshotted at its execution, launches executed before the pools
+    /// were stored on their own keep the first page on the pilot
+    pub fn bid_pools(
+        &self,
+        storage: &dyn Storage,
+        sale: usize,
+    ) -> StdResult<Option<BidPoolsResponse>> {
+        Ok(BID_POOLS
+            .may_load(storage, (self.idx.u128(), sale as u8))?
+            .or_else(|| {
+                self.pilots
+                    .get(sale)
+                    .and_then(|pilot| pilot.bid_pools_snapshot.clone())
+            }))
+    }
+
+    /// round and amount of every Pilot sale of the launch, see `Tokenomics::sale_allocations`
+    pub fn sale_allocations(&self) -> Vec<(u8, Uint128)> {
+        self.tokenomics
+            .as_ref()
+            .map(|t| t.sale_allocations())
+            .unwrap_or_default()
+    }
+
+    /// the last round started on Pilot, rounds are started in order so only this one can be live
+    pub fn started_round(&self) -> Option<u8> {
+        let allocations = self.sale_allocations();
+        self.pilots
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.idx.is_some())
+            .filter_map(|(sale, _)| allocations.get(sale).map(|(round, _)| *round))
+            .max()
+    }
+
+    /// the next round when a keeper can start it, the token is managed so no owner funds are needed,
+    /// the round has a single sale and the sale has opened
+    pub fn keeper_round(&self, now: &Timestamp) -> Option<u8> {
+        if !matches!(
+            self.status,
+            LaunchStatus::Scheduled | LaunchStatus::SaleExecuted
+        ) || !self.token.as_ref().map_or(false, |t| t.is_managed)
+        {
+            return None;
+        }
+
+        let round = self.started_round().map_or(0, |r| r + 1);
+        let sales: Vec<usize> = self
+            .sale_allocations()
+            .iter()
+            .enumerate()
+            .filter(|(_, (r, _))| *r == round)
+            .map(|(sale, _)| sale)
+            .collect();
+        match sales.as_slice() {
+            [sale] => self
+                .pilots
+                .get(*sale)
+                .filter(|p| p.idx.is_none() && p.sale.opens.seconds() <= now.seconds())
+                .map(|_| round),
+            _ => None,
+        }
+    }
+
+    /// whether every round has been started and the last one is no longer live, PostLaunch can
+    /// then be cranked once Pilot has executed the sales
+    pub fn all_rounds_started(&self) -> bool {
+        self.status == LaunchStatus::SaleExecuted
+            && self.sale_allocations().len() == self.pilots.len()
+            && self.pilots.iter().all(|p| p.idx.is_some())
+    }
+
+    /// whether the sale is the first of its round, the launch deposit covers its Pilot deposit
+    /// while the owner deposits for the other sales of the round
+    pub fn is_round_lead(&self, sale: usize) -> bool {
+        let allocations = self.sale_allocations();
+        allocations
+            .get(sale)
+            .and_then(|(round, _)| allocations.iter().position(|(r, _)| r == round))
+            == Some(sale)
+    }
+
+    /// index of the sale of a round recipient in the flattened sale allocations
+    pub fn sale_index(&self, round: u8, recipient: Option<u8>) -> Option<usize> {
+        self.sale_allocations()
+            .iter()
+            .enumerate()
+            .filter(|(_, (r, _))| *r == round)
+            .nth(recipient.unwrap_or(0) as usize)
+            .map(|(sale_idx, _)| sale_idx)
+    }
+
+    /// the round a message addresses and the indexes of its scheduled sales,
+    /// defaults to the last started round
+    pub fn round_sales(&self, round: Option<u8>) -> Result<(u8, Vec<usize>), ContractError> {
+        let round = round
+            .or(self.started_round())
+            .ok_or(ContractError::InvalidRound("none".to_string()))?;
+        let sales: Vec<usize> = self
+            .sale_allocations()
+            .iter()
+            .enumerate()
+            .filter(|(sale, (r, _))| *r == round && *sale < self.pilots.len())
+            .map(|(sale, _)| sale)
+            .collect();
+        ensure!(
+            !sales.is_empty(),
+            ContractError::InvalidRound(round.to_string())
+        );
+        Ok((round, sales))
+    }
+
+    /// counts the launches of the owner that have not reached a terminal status, from the
+    /// owner and status index entries so that no launch is loaded
+    pub fn active_count_by_owner(storage: &dyn Storage, owner: &Addr) -> StdResult<u32> {
+        let count = LaunchStatus::active()
+            .iter()
+            .map(|status| {
+                launch()
+                    .idx
+                    .owner_status
+                    .prefix((owner.to_string(), status.to_string()))
+                    .keys_raw(storage, None, None, Order::Ascending)
+                    .count()
+            })
+            .sum::<usize>();
+        Ok(count as u32)
+    }
+
+    /// moves the launch to the status that follows the action and records it in the history
+    /// errors when the action is not allowed in the current status
+    pub fn transition(
+        &mut self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        action: LaunchAction,
+    ) -> Result<LaunchStatus, ContractError> {
+        let from = self.status.clone();
+        let status = next_status(&from, &action).ok_or_else(|| {
+            ContractError::InvalidTransition(from.to_string(), action.to_string())
+        })?;
+        self.status = status.clone();
+        self.record(storage, env, sender, action, from)?;
+        Ok(status)
+    }
+
+    /// appends an entry for the action to the launch history
+    pub fn record(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        action: LaunchAction,
+        from: LaunchStatus,
+    ) -> StdResult<()> {
+        let idx = self.idx.u128();
+        let seq = match LAUNCH_HISTORY
+            .prefix(idx)
+            .keys(storage, None, None, Order::Descending)
+            .next()
+        {
+            Some(Ok(x)) => x + 1,
+            _ => 0,
+        };
+
+        LAUNCH_HISTORY.save(
+            storage,
+            (idx, seq),
+            &LaunchHistoryEntry {
+                seq,
+                height: env.block.height,
+                time: env.block.time,
+                sender: sender.clone(),
+                action,
+                from,
+                to: self.status.clone(),
+            },
+        )
+    }
+
     pub fn is_owner(&self, addr: &Addr) -> Result<bool, ContractError> {
         ensure!(self.owner == addr, ContractError::Unauthorized {});
         Ok(true)
     }
 }
+
+/// the launch state machine, returns the status that follows the action or None when the
+/// action is not allowed in the given status
+pub fn next_status(status: &LaunchStatus, action: &LaunchAction) -> Option<LaunchStatus> {
+    use LaunchAction as A;
+    use LaunchStatus as S;
+
+    match (status, action) {
+        (S::Created | S::TokenSet, A::Token) => Some(S::TokenSet),
+        (S::TokenSet | S::TokenomicsSet, A::Tokenomics) => Some(S::TokenomicsSet),
+        (S::TokenomicsSet | S::Scheduled, A::PilotSchedule) => Some(S::Scheduled),
+        // later rounds can be scheduled while earlier rounds are live or executed
+        (S::SaleLive, A::PilotSchedule) => Some(S::SaleLive),
+        (S::SaleExecuted, A::PilotSchedule) => Some(S::SaleExecuted),
+        (S::Scheduled | S::SaleExecuted, A::PilotStart) => Some(S::SaleLive),
+        (S::SaleLive, A::UpdateDescription) => Some(S::SaleLive),
+        // the allowlist can be changed until a round goes live and between rounds
+        (
+            s @ (S::Created | S::TokenSet | S::TokenomicsSet | S::Scheduled | S::SaleExecuted),
+            A::Allowlist,
+        ) => Some(s.clone()),
+        (
+            s @ (S::Created
+            | S::TokenSet
+            | S::TokenomicsSet
+            | S::Scheduled
+            | S::SaleLive
+            | S::SaleExecuted),
+            A::PricePrecision,
+        ) => Some(s.clone()),
+        (S::SaleLive, A::PilotExecute) => Some(S::SaleExecuted),
+        (S::SaleLive, A::PilotRetract) => Some(S::Retracted),
+        (S::SaleLive, A::RoundRetract) => Some(S::SaleExecuted),
+        // SaleLive is allowed for when Execute is called on the Pilot Contract directly
+        (S::SaleLive | S::SaleExecuted, A::PostLaunch) => Some(S::LiquidityDeployed),
+        (S::LiquidityDeployed, A::BowCallback) => Some(S::LiquidityDeployed),
+        (S::LiquidityDeployed, A::LaunchFin) => Some(S::Finalized),
+        (S::Created | S::TokenSet | S::TokenomicsSet | S::Scheduled, A::Cancel) => {
+            Some(S::Cancelled)
+        }
+        _ => None,
+    }
+}
@@ -1,9 +1,14 @@
 //! This contract provides an interface to Pilot sales and orchestrates the
 //! creation of DeFi instruments as required by the launcher
+pub mod allowlist;
 pub mod contract;
 pub mod error;
 pub mod launch;
+pub mod migrations;
 pub mod msg;
+pub mod post_launch;
+pub mod pricing;
+pub mod raise;
 pub mod state;
 
 #[cfg(test)]
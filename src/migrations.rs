@@ -0,0 +1,217 @@
+//! Storage migrations for launches saved by previous versions of the contract
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, DepsMut, Env, Order, StdResult, Uint128};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, MultiIndex, UniqueIndex};
+use kujira::KujiraQuery;
+use kujira_pilot::Status;
+
+use crate::{
+    launch::Launch,
+    msg::{Bow, Config, Fin, LaunchAction, LaunchStatus, Pilot, Token, Tokenomics},
+    state::{launch, MIGRATION_CURSOR},
+};
+
+const LAUNCH_NAMESPACE: &str = "launch";
+/// launches migrated in a single call unless a limit is given
+const DEFAULT_MIGRATION_LIMIT: u32 = 30;
+
+/// launch statuses up to v0.2
+#[cw_serde]
+pub enum LegacyLaunchStatus {
+    Created = 1,
+    Planned = 2,
+    InProgress = 3,
+    Completed = 4,
+}
+
+impl fmt::Display for LegacyLaunchStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// launch as stored up to v0.2
+#[cw_serde]
+pub struct LegacyLaunch {
+    pub idx: Uint128,
+    pub owner: Addr,
+    pub deposit: Coin,
+    pub terms_conditions_accepted: Option<bool>,
+    pub status: LegacyLaunchStatus,
+    pub token: Option<Token>,
+    pub tokenomics: Option<Tokenomics>,
+    pub pilot: Option<Pilot>,
+    pub fin: Option<Fin>,
+    pub bow: Option<Bow>,
+}
+
+pub struct LegacyLaunchIndexes<'a> {
+    pub key: UniqueIndex<'a, u128, LegacyLaunch>,
+    pub owner: MultiIndex<'a, String, LegacyLaunch, u128>,
+    pub status: MultiIndex<'a, String, LegacyLaunch, u128>,
+    pub pilot_idx: MultiIndex<'a, u128, LegacyLaunch, u128>,
+}
+
+impl<'a> IndexList<LegacyLaunch> for LegacyLaunchIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<LegacyLaunch>> + '_> {
+        let v: Vec<&dyn Index<LegacyLaunch>> =
+            vec![&self.key, &self.owner, &self.status, &self.pilot_idx];
+        Box::new(v.into_iter())
+    }
+}
+
+/// mirrors the namespaces and indexes of `state::launch` so that legacy entries and their
+/// index entries can be removed before the migrated launch is saved
+pub fn legacy_launch<'a>() -> IndexedMap<'a, u128, LegacyLaunch, LegacyLaunchIndexes<'a>> {
+    let indexes = LegacyLaunchIndexes {
+        key: UniqueIndex::new(|d| d.idx.u128(), "launch__key"),
+        owner: MultiIndex::new(
+            |_d, d| d.owner.clone().to_string(),
+            LAUNCH_NAMESPACE,
+            "launch__owner",
+        ),
+        status: MultiIndex::new(
+            |_d, d| d.status.clone().to_string(),
+            LAUNCH_NAMESPACE,
+            "launch__status",
+        ),
+        pilot_idx: MultiIndex::new(
+            |_d, d| {
+                d.pilot
+                    .clone()
+                    .and_then(|pilot| pilot.idx)
+                    .map_or(0, |idx| idx.u128())
+            },
+            LAUNCH_NAMESPACE,
+            "launch__pilot_idx",
+        ),
+    };
+    IndexedMap::new(LAUNCH_NAMESPACE, indexes)
+}
+
+/// maps the legacy status onto the launch state machine
+/// Completed was used for executed, retracted and post launched sales, post launched sales have
+/// their FIN pair, which is finalized once it no longer bootstraps, the pilot sale is queried to
+/// tell executed and retracted sales apart
+fn migrate_status(
+    deps: &DepsMut<KujiraQuery>,
+    config: &Config,
+    legacy: &LegacyLaunch,
+) -> LaunchStatus {
+    match legacy.status {
+        LegacyLaunchStatus::Created => match (&legacy.token, &legacy.tokenomics) {
+            (Some(_), Some(_)) => LaunchStatus::TokenomicsSet,
+            (Some(_), None) => LaunchStatus::TokenSet,
+            _ => LaunchStatus::Created,
+        },
+        LegacyLaunchStatus::Planned => LaunchStatus::Scheduled,
+        LegacyLaunchStatus::InProgress => LaunchStatus::SaleLive,
+        LegacyLaunchStatus::Completed => {
+            if let Some(fin) = legacy.fin.clone().and_then(|fin| fin.contract_address) {
+                let fin_config: StdResult<kujira_fin::ConfigResponse> = deps
+                    .querier
+                    .query_wasm_smart(fin, &kujira_fin::QueryMsg::Config {});
+                return match fin_config {
+                    Ok(fin_config) if !fin_config.is_bootstrapping => LaunchStatus::Finalized,
+                    _ => LaunchStatus::LiquidityDeployed,
+                };
+            }
+            let sale_idx = legacy.pilot.clone().and_then(|pilot| pilot.idx);
+            let sale: Option<kujira_pilot::SaleResponse> = sale_idx.and_then(|idx| {
+                deps.querier
+                    .query_wasm_smart(
+                        config.pilot.pilot_contract.clone(),
+                        &kujira_pilot::QueryMsg::Sale { idx },
+                    )
+                    .ok()
+            });
+            // a sale that cannot be queried is left to PostLaunch, which checks its status again
+            match sale.map(|sale| sale.status) {
+                Some(Status::Retracted { .. }) => LaunchStatus::Retracted,
+                Some(Status::Executed { .. }) | None => LaunchStatus::SaleExecuted,
+                Some(_) => LaunchStatus::SaleLive,
+            }
+        }
+    }
+}
+
+/// migrates a batch of the launches still stored with a legacy status, from the cursor of the
+/// previous batch, and returns the number migrated and whether launches are left to migrate
+/// launches that are already migrated fail to load as legacy launches and are skipped
+pub fn migrate_launches(
+    deps: DepsMut<KujiraQuery>,
+    env: &Env,
+    sender: &Addr,
+    config: &Config,
+    limit: Option<u32>,
+) -> StdResult<(u64, bool)> {
+    let cursor = MIGRATION_CURSOR.may_load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_MIGRATION_LIMIT) as usize;
+    let mut keys = launch()
+        .keys(
+            deps.storage,
+            cursor.map(Bound::inclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit + 1)
+        .collect::<StdResult<Vec<u128>>>()?;
+
+    // the key after the batch is where the next one starts
+    let mut next = if keys.len() > limit { keys.pop() } else { None };
+
+    let mut migrated = 0u64;
+    for key in keys {
+        let legacy = match legacy_launch().may_load(deps.storage, key) {
+            Ok(Some(legacy)) => legacy,
+            _ => continue,
+        };
+
+        let status = migrate_status(&deps, config, &legacy);
+        let migrated_launch = Launch {
+            idx: legacy.idx,
+            owner: legacy.owner.clone(),
+            deposit: legacy.deposit.clone(),
+            terms_conditions_accepted: legacy.terms_conditions_accepted,
+            status,
+            token: legacy.token,
+            tokenomics: legacy.tokenomics,
+            pilots: legacy.pilot.into_iter().collect(),
+            fin: legacy.fin,
+            bow: legacy.bow,
+            liquidity: None,
+            existing_fin: None,
+            markets: None,
+            allowlist: None,
+            keeper_rewards: Uint128::zero(),
+            prefunded: vec![],
+            price_precision: None,
+            market_params: None,
+        };
+
+        legacy_launch().remove(deps.storage, key)?;
+        migrated_launch.save(deps.storage)?;
+        migrated_launch.record(
+            deps.storage,
+            env,
+            sender,
+            LaunchAction::Migrate,
+            migrated_launch.status.clone(),
+        )?;
+        migrated += 1;
+    }
+
+    // legacy launches precede every launch created since, so a batch without any means none are
+    // left and later upgrades do not block Create on a pending migration
+    if migrated == 0 {
+        next = None;
+    }
+    match next {
+        Some(next) => MIGRATION_CURSOR.save(deps.storage, &next)?,
+        None => MIGRATION_CURSOR.remove(deps.storage),
+    }
+
+    Ok((migrated, next.is_some()))
+}
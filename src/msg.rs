@@ -1,12 +1,16 @@
 use std::fmt;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, Coin, Decimal, Decimal256, Uint128};
+use cosmwasm_std::{
+    ensure, Addr, Binary, Coin, CosmosMsg, Decimal, Decimal256, Timestamp, Uint128,
+};
 use fuzion_flows::FlowCreate;
-use kujira::{CallbackMsg, Denom};
+use kujira::{CallbackMsg, Denom, KujiraMsg};
 use kujira_orca::BidPoolsResponse;
 use kujira_pilot::{CreateOrca, CreateSale};
 
+use crate::ContractError;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub owner: Addr,
@@ -16,10 +20,14 @@ pub struct InstantiateMsg {
     pub flows: FlowsConfig,
     pub fin: FinConfig,
     pub bow: BowConfig,
+    pub launch: LaunchConfig,
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// launches migrated by the migration, the others are migrated with MigrateLaunches
+    pub limit: Option<u32>,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -32,6 +40,7 @@ pub enum ExecuteMsg {
         flows: Option<FlowsConfig>,
         fin: Box<Option<FinConfig>>,
         bow: Option<BowConfig>,
+        launch: Option<LaunchConfig>,
     },
     /// creates a sale and requires the deposit to be paid
     Create {
@@ -53,25 +62,71 @@ pub enum ExecuteMsg {
         idx: Uint128,
         categories: Vec<TokenomicsCategories>,
     },
-    /// schedules the pilot sale with the required sale and orca information
+    /// schedules the pilot sale of a round with the required sale and orca information
+    /// each round sells the allocation of the sale category at the same position, defaults to the first round
     PilotSchedule {
         idx: Uint128,
+        round: Option<u8>,
+        /// recipient of the round's sale category sold by this sale, every recipient is sold in its
+        /// own Pilot sale, defaults to the first recipient
+        recipient: Option<u8>,
         sale: CreateSale,
         orca: CreateOrca,
+        /// FIN pair of the token and the bid denom to provide liquidity on instead of a new one
+        existing_fin: Option<Addr>,
+        /// FIN fees and BOW curve of the markets of the launch instead of those of the config
+        market: Option<MarketParams>,
     },
-    /// starts the pilot sale
+    /// starts the pilot sales of a round, rounds are started in order once the previous round has
+    /// been executed or retracted, defaults to the next round
+    /// every sale of the round after the first one requires the Pilot deposit to be sent
     PilotStart {
         idx: Uint128,
+        round: Option<u8>,
     },
+    /// starts the next round of a managed token launch once its sale has opened, callable by anyone
+    /// only rounds of a single sale can be started this way as no owner funds are needed
+    KeeperStart {
+        idx: Uint128,
+    },
+    /// executes the sales of the live round, the round is optional
     PilotExecute {
         idx: Uint128,
+        round: Option<u8>,
+    },
+    /// executes the sales of the last round and performs PostLaunch in the same transaction,
+    /// the funds of PostLaunch are sent with it
+    Settle {
+        idx: Uint128,
     },
+    /// retracts the sales of the live round, the round is optional
     PilotRetract {
         idx: Uint128,
+        round: Option<u8>,
+    },
+    /// cancels a launch before the pilot sale is started and refunds the deposit
+    CancelLaunch {
+        idx: Uint128,
     },
     PostLaunch {
         idx: Uint128,
     },
+    /// overrides the decimal places of the FIN prices of the launch, None uses the policy of the
+    /// config, the decimal places have to be within the bounds of the policy
+    PricePrecision {
+        idx: Uint128,
+        decimal_places: Option<u8>,
+    },
+    /// adds the funds sent to the prefunded BOW creation fee and keeper rewards of the launch, see
+    /// KeeperStart and CrankPostLaunch
+    Prefund {
+        idx: Uint128,
+    },
+    /// executes PostLaunch for a managed token launch, callable by anyone once every round has been
+    /// started and executed or retracted, the BOW creation fee is paid from the prefunded funds
+    CrankPostLaunch {
+        idx: Uint128,
+    },
     LaunchFin {
         idx: Uint128,
     },
@@ -90,6 +145,40 @@ pub enum ExecuteMsg {
         idx: Uint128,
         description: String,
     },
+    /// sets or removes the advisory allowlist of the launch, only before a round is live
+    SetAllowlist {
+        idx: Uint128,
+        allowlist: Option<Allowlist>,
+    },
+    /// places the bid sent on the Orca queue of the live sale raising its denom
+    /// only for addresses of the allowlist, the cap has to match the one the leaf was built with
+    /// and applies to the bids of each bid denom
+    AllowlistBid {
+        idx: Uint128,
+        premium_slot: u8,
+        cap: Option<Uint128>,
+        proof: Vec<String>,
+    },
+    /// retracts a bid Keiko placed for the sender through AllowlistBid and sends the bid denom
+    /// back to them while its sale is live, the bids of executed or retracted sales are claimed
+    /// with ClaimForBidder
+    RetractAllowlistBid {
+        idx: Uint128,
+        /// index of the Pilot sale in the launch, each sale has its own Orca queue
+        sale: u8,
+        bid_idx: Uint128,
+    },
+    /// migrates the next batch of launches stored with a legacy status, config owner only
+    MigrateLaunches {
+        limit: Option<u32>,
+    },
+    /// claims the tokens bought and retracts the unfilled amount of the bids placed through
+    /// AllowlistBid for the bidder once their sale is executed, the funds are sent to the bidder
+    /// callable by anyone
+    ClaimForBidder {
+        idx: Uint128,
+        bidder: Addr,
+    },
     Callback(CallbackMsg),
 }
 
@@ -119,6 +208,66 @@ pub enum QueryMsg {
         start_after: Option<Uint128>,
         limit: Option<u8>,
     },
+    #[returns(Vec<LaunchHistoryEntry>)]
+    LaunchHistory {
+        idx: Uint128,
+        start_after: Option<u64>,
+        limit: Option<u8>,
+    },
+    /// previews the PostLaunch messages against the executed Pilot sale without sending them
+    #[returns(SimulatePostLaunchResponse)]
+    SimulatePostLaunch { idx: Uint128 },
+    /// range of the raise of a sale of a round recipient that sells out at the price and
+    /// orca parameters given, the recipient defaults to the first one of the round
+    #[returns(RaiseEstimate)]
+    EstimateRaise {
+        idx: Uint128,
+        round: Option<u8>,
+        recipient: Option<u8>,
+        price: Decimal,
+        orca: CreateOrca,
+    },
+    /// FIN and BOW addresses the launch deploys to in PostLaunch
+    #[returns(PredictedAddressesResponse)]
+    PredictedAddresses { idx: Uint128 },
+    /// launches of managed tokens whose rounds have all been started and that are waiting for
+    /// PostLaunch, newest first
+    #[returns(Vec<crate::launch::Launch>)]
+    LaunchesAwaitingPostLaunch {
+        start_after: Option<Uint128>,
+        limit: Option<u8>,
+    },
+    /// launches whose next round can be started with KeeperStart, newest first
+    #[returns(Vec<crate::launch::Launch>)]
+    LaunchesReadyToStart {
+        start_after: Option<Uint128>,
+        limit: Option<u8>,
+    },
+    /// whether the address can bid through AllowlistBid and how much of its cap is left for
+    /// the bid denom
+    #[returns(AllowlistEligibilityResponse)]
+    AllowlistEligibility {
+        idx: Uint128,
+        address: Addr,
+        denom: Denom,
+        cap: Option<Uint128>,
+        proof: Vec<String>,
+    },
+    /// bids placed through AllowlistBid by the bidder
+    #[returns(Vec<AllowlistBid>)]
+    AllowlistBids { idx: Uint128, bidder: Addr },
+    /// Orca bids of every executed sale of the launch, from the bid pools snapshotted before the
+    /// execution
+    #[returns(Vec<SaleAnalytics>)]
+    SaleAnalytics { idx: Uint128 },
+    /// Orca bids of the bidder left on the executed sales of the launches, newest launch first
+    /// the limit is on the launches looked at, not on the positions returned
+    #[returns(Vec<BidderPosition>)]
+    BidderPositions {
+        bidder: Addr,
+        start_after: Option<Uint128>,
+        limit: Option<u8>,
+    },
 }
 
 #[cw_serde]
@@ -137,11 +286,85 @@ pub struct Tokenomics {
     pub categories: Vec<TokenomicsCategories>,
 }
 
+impl Tokenomics {
+    /// sale categories in order, each one is sold in its own Pilot round
+    pub fn sale_categories(&self) -> Vec<TokenomicsCategories> {
+        self.categories
+            .iter()
+            .filter(|c| c.category_type == CategoryTypes::Sale)
+            .cloned()
+            .collect()
+    }
+
+    /// round and amount of every Pilot sale in order, each recipient of a sale category is sold in
+    /// its own Pilot sale and the sales of a round run at the same time
+    pub fn sale_allocations(&self) -> Vec<(u8, Uint128)> {
+        self.sale_categories()
+            .iter()
+            .enumerate()
+            .flat_map(|(round, category)| {
+                category
+                    .recipients
+                    .iter()
+                    .map(move |recipient| (round as u8, recipient.amount))
+            })
+            .collect()
+    }
+}
+
 #[cw_serde]
 pub struct TokenomicsCategories {
     pub label: String,
     pub category_type: CategoryTypes,
     pub recipients: Vec<TokenomicsRecipient>,
+    /// only for the liquidity category, where the liquidity allocation is deposited
+    /// defaults to a single BOW market maker on the FIN pair created by the launch
+    pub liquidity: Option<Vec<LiquidityTarget>>,
+    /// only for the liquidity category, how the LP tokens of every BOW deposit are vested
+    /// defaults to a single schedule to the Pilot beneficiary with the configured cliff and duration
+    pub lp_vesting: Option<Vec<LpVestingTranche>>,
+}
+
+#[cw_serde]
+pub struct LpVestingTranche {
+    /// receives the vested LP tokens, defaults to the Pilot beneficiary
+    pub taker: Option<Addr>,
+    /// share of the LP tokens, the shares of all tranches add up to one
+    pub share: Decimal,
+    /// seconds after the BOW deposit
+    pub cliff: u64,
+    /// seconds after the BOW deposit
+    pub duration: u64,
+}
+
+#[cw_serde]
+pub struct LiquidityTarget {
+    pub destination: LiquidityDestination,
+    /// share of the liquidity allocation, the shares of all targets add up to one
+    pub share: Decimal,
+}
+
+#[cw_serde]
+pub enum LiquidityDestination {
+    /// a new BOW market maker with the configured curve on the FIN pair created by the launch
+    BowMarketMaker,
+    /// a new BOW market maker with its own xyk or stable curve on the FIN pair created by the launch
+    BowStable {
+        intervals: Vec<Decimal>,
+        fee: Decimal,
+        amp: Decimal,
+    },
+    /// an existing BOW pool on an existing FIN pair
+    ExistingFin { fin: Addr, bow: Addr },
+}
+
+/// liquidity deposited by PostLaunch, the LP tokens are vested when the BOW callback is received
+#[cw_serde]
+pub struct LiquidityPosition {
+    pub destination: LiquidityDestination,
+    pub bow: Addr,
+    pub deposit: Vec<Coin>,
+    pub lp: Option<Coin>,
 }
 
 #[cw_serde]
@@ -164,7 +387,29 @@ pub struct Pilot {
     pub beneficiary: Addr,
     pub sale: CreateSale,
     pub orca: CreateOrca,
+    /// Orca queue of the sale, recorded when the sale is created
+    #[serde(default)]
+    pub orca_address: Option<Addr>,
+    /// first page of the bid pools of sales executed before the pools were stored in `BID_POOLS`
     pub bid_pools_snapshot: Option<BidPoolsResponse>,
+    pub result: Option<SaleResult>,
+}
+
+#[cw_serde]
+pub struct SaleResult {
+    pub at: Timestamp,
+    pub raise_total: Uint128,
+    pub raise_fee: Uint128,
+    pub raise_amount: Uint128,
+}
+
+/// FIN pair of the launch token against one bid denom
+#[cw_serde]
+pub struct Market {
+    pub bid_denom: Denom,
+    pub fin: Addr,
+    /// the pair was not created by the launch
+    pub existing: bool,
 }
 
 #[cw_serde]
@@ -194,14 +439,91 @@ pub struct TokenomicsConfig {
     pub minimum_liquidity_one_side: Decimal,
     pub default_lp_vest_cliff: u64,
     pub default_lp_vest_duration: u64,
+    #[serde(default)]
+    pub lp_vest_bounds: LpVestBounds,
+}
+
+/// bounds of the LP vesting tranches a launch can choose, in seconds
+#[cw_serde]
+#[derive(Default)]
+pub struct LpVestBounds {
+    pub min_cliff: u64,
+    pub max_cliff: Option<u64>,
+    pub min_duration: u64,
+    pub max_duration: Option<u64>,
 }
 
 #[cw_serde]
 pub enum LaunchStatus {
     Created = 1,
-    Planned = 2,
-    InProgress = 3,
-    Completed = 4,
+    TokenSet = 2,
+    TokenomicsSet = 3,
+    Scheduled = 4,
+    SaleLive = 5,
+    SaleExecuted = 6,
+    Retracted = 7,
+    LiquidityDeployed = 8,
+    Finalized = 9,
+    Cancelled = 10,
+}
+
+impl LaunchStatus {
+    /// terminal launches no longer count towards the active launches of an owner
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            LaunchStatus::Retracted | LaunchStatus::Finalized | LaunchStatus::Cancelled
+        )
+    }
+
+    /// statuses that count towards the active launches of an owner
+    pub fn active() -> Vec<LaunchStatus> {
+        vec![
+            LaunchStatus::Created,
+            LaunchStatus::TokenSet,
+            LaunchStatus::TokenomicsSet,
+            LaunchStatus::Scheduled,
+            LaunchStatus::SaleLive,
+            LaunchStatus::SaleExecuted,
+            LaunchStatus::LiquidityDeployed,
+        ]
+    }
+}
+
+/// actions that move a launch through its statuses, see `Launch::transition`
+/// Create and Update are only recorded in the launch history and are not transitions
+#[cw_serde]
+pub enum LaunchAction {
+    Create,
+    Update,
+    Token,
+    Tokenomics,
+    PilotSchedule,
+    PilotStart,
+    PilotExecute,
+    PilotRetract,
+    /// retracts a later round once an earlier round has been executed, the launch continues
+    RoundRetract,
+    PostLaunch,
+    BowCallback,
+    LaunchFin,
+    UpdateDescription,
+    Cancel,
+    Allowlist,
+    PricePrecision,
+    /// the actions below leave the status of the launch unchanged
+    Prefund,
+    AllowlistBid,
+    RetractAllowlistBid,
+    ClaimForBidder,
+    /// the launch was migrated from a legacy status, by the migration or MigrateLaunches
+    Migrate,
+}
+
+impl fmt::Display for LaunchAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
 }
 
 impl fmt::Display for LaunchStatus {
@@ -210,6 +532,144 @@ impl fmt::Display for LaunchStatus {
     }
 }
 
+#[cw_serde]
+pub struct LaunchHistoryEntry {
+    pub seq: u64,
+    pub height: u64,
+    pub time: Timestamp,
+    pub sender: Addr,
+    pub action: LaunchAction,
+    pub from: LaunchStatus,
+    pub to: LaunchStatus,
+}
+
+/// Raise of a sale that sells out, in bid denom units
+#[cw_serde]
+pub struct RaiseEstimate {
+    /// every token sold at the premium of the highest slot, PilotSchedule requires it to be above
+    /// the minimum raise amount
+    pub min_raise: Uint128,
+    /// every token sold at the sale price
+    pub max_raise: Uint128,
+    /// the minimum raise net of the Pilot sale fee
+    pub net_min_raise: Uint128,
+}
+
+#[cw_serde]
+pub struct SimulatePostLaunchResponse {
+    pub average_price: Decimal256,
+    pub price_precision_decimals: u8,
+    pub lp_stable_amount: Uint128,
+    pub beneficiary_funds: Coin,
+    pub fin_address: Addr,
+    pub bow_address: Addr,
+    /// one entry per bid denom raised, the fields above are those of the first one
+    pub markets: Vec<MarketSimulation>,
+    pub liquidity: Vec<LiquidityPosition>,
+    /// funds to send with PostLaunch
+    pub required_deposit: Vec<Coin>,
+    pub messages: Vec<CosmosMsg<KujiraMsg>>,
+}
+
+#[cw_serde]
+pub struct MarketSimulation {
+    pub bid_denom: Denom,
+    pub fin_address: Addr,
+    pub average_price: Decimal256,
+    pub price_precision_decimals: u8,
+    pub lp_stable_amount: Uint128,
+    /// tokens seeded as liquidity, less than the liquidity allocation when priced above the average
+    pub lp_token_amount: Uint128,
+    pub beneficiary_funds: Coin,
+}
+
+/// addresses allowed to bid through Keiko
+/// the leaves are the sha256 hashes of the address followed by its cap when it has one
+/// the allowlist is advisory: Pilot creates public Orca queues, so anyone can still bid on them
+/// directly, only the bids placed through AllowlistBid are checked and capped
+#[cw_serde]
+pub struct Allowlist {
+    /// hex encoded merkle root
+    pub merkle_root: String,
+}
+
+/// bid placed on an Orca queue by Keiko for an allowlisted bidder
+#[cw_serde]
+pub struct AllowlistBid {
+    /// index of the Pilot sale in the launch
+    pub sale: u8,
+    pub bid_idx: Uint128,
+    pub amount: Coin,
+    /// set once ClaimForBidder or RetractAllowlistBid has claimed and retracted the bid
+    #[serde(default)]
+    pub claimed: bool,
+}
+
+#[cw_serde]
+pub struct AllowlistEligibilityResponse {
+    pub eligible: bool,
+    /// total of the bids placed through AllowlistBid in the bid denom
+    pub bid: Uint128,
+    /// cap left, none without a cap
+    pub remaining: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct PredictedAddressesResponse {
+    pub fin: Addr,
+    pub bow: Addr,
+}
+
+/// Orca bids of an executed sale, amounts are in bid denom units
+#[cw_serde]
+pub struct SaleAnalytics {
+    /// index of the sale in the pilots of the launch
+    pub sale: u8,
+    pub round: u8,
+    pub bid_denom: Denom,
+    /// tokens for sale
+    pub amount: Uint128,
+    pub total_bid_amount: Uint128,
+    /// bids that bought tokens
+    pub filled_amount: Uint128,
+    pub unfilled_amount: Uint128,
+    /// premium of the highest slot the sale filled, none when the bids did not cover the sale
+    pub clearing_premium: Option<Decimal>,
+    /// price in whole bid denoms per whole token the tokens cleared at
+    pub clearing_price: Option<Decimal>,
+    pub slots: Vec<SlotAnalytics>,
+}
+
+/// Orca bid of a bidder on an executed sale with funds left to claim or retract
+#[cw_serde]
+pub struct BidderPosition {
+    pub idx: Uint128,
+    /// index of the Pilot sale in the launch
+    pub sale: u8,
+    pub orca_address: Addr,
+    pub bid_idx: Uint128,
+    pub premium_slot: u8,
+    /// bid denom left in the bid
+    pub retractable: Coin,
+    /// tokens bought by the bid
+    pub claimable: Coin,
+    /// placed by Keiko through AllowlistBid and claimed with ClaimForBidder, the bidder claims
+    /// and retracts the other bids on Orca
+    pub keiko: bool,
+    /// the sale is still live, ClaimForBidder leaves its bids on Orca until it is executed or
+    /// retracted
+    pub live: bool,
+}
+
+#[cw_serde]
+pub struct SlotAnalytics {
+    pub slot: u8,
+    pub premium_rate: Decimal,
+    pub total_bid_amount: Uint128,
+    pub filled_amount: Uint128,
+    pub unfilled_amount: Uint128,
+}
+
 #[cw_serde]
 pub struct BidDenoms {
     pub denom: Denom,
@@ -222,6 +682,23 @@ pub struct PilotConfig {
     pub pilot_contract: Addr,
     pub allowed_bid_denoms: Vec<BidDenoms>,
     pub min_raise_amount: Uint128,
+    #[serde(default)]
+    pub sale_bounds: SaleBounds,
+}
+
+/// bounds Keiko sets on the Pilot sales and Orca queues a launch can schedule, on top of what Pilot
+/// and Orca accept, times are in seconds
+#[cw_serde]
+#[derive(Default)]
+pub struct SaleBounds {
+    pub min_opens_delay: u64,
+    pub max_opens_delay: Option<u64>,
+    pub min_duration: u64,
+    pub max_duration: Option<u64>,
+    pub min_price: Decimal,
+    pub max_price: Option<Decimal>,
+    pub max_slot: Option<u8>,
+    pub max_premium: Option<Decimal>,
 }
 
 #[cw_serde]
@@ -237,6 +714,68 @@ pub struct FinConfig {
     pub fee_maker: Decimal256,
     pub fee_taker: Decimal256,
     pub fee_address: Addr,
+    #[serde(default)]
+    pub price_precision: PricePrecisionPolicy,
+    #[serde(default)]
+    pub fee_bounds: FinFeeBounds,
+}
+
+/// bounds of the FIN fees a launch can choose
+#[cw_serde]
+#[derive(Default)]
+pub struct FinFeeBounds {
+    pub min_fee_maker: Decimal256,
+    pub max_fee_maker: Option<Decimal256>,
+    pub min_fee_taker: Decimal256,
+    pub max_fee_taker: Option<Decimal256>,
+}
+
+/// precision of the FIN pairs created by PostLaunch, the price keeps the significant digits within
+/// the decimal places bounds
+#[cw_serde]
+pub struct PricePrecisionPolicy {
+    pub significant_digits: u8,
+    pub min_decimal_places: u8,
+    pub max_decimal_places: u8,
+}
+
+impl Default for PricePrecisionPolicy {
+    fn default() -> Self {
+        Self {
+            significant_digits: 5,
+            min_decimal_places: 3,
+            max_decimal_places: 18,
+        }
+    }
+}
+
+impl PricePrecisionPolicy {
+    /// ensures the policy keeps at least one significant digit and its decimal places bounds are
+    /// ordered and within the 18 decimal places of a price
+    pub fn validate(&self) -> Result<(), ContractError> {
+        ensure!(
+            self.significant_digits > 0,
+            ContractError::InvalidPricePrecisionPolicy(
+                "significant_digits".to_string(),
+                self.significant_digits.to_string()
+            )
+        );
+        ensure!(
+            self.min_decimal_places <= self.max_decimal_places,
+            ContractError::InvalidPricePrecisionPolicy(
+                "min_decimal_places".to_string(),
+                self.min_decimal_places.to_string()
+            )
+        );
+        ensure!(
+            self.max_decimal_places <= 18,
+            ContractError::InvalidPricePrecisionPolicy(
+                "max_decimal_places".to_string(),
+                self.max_decimal_places.to_string()
+            )
+        );
+        Ok(())
+    }
 }
 
 #[cw_serde]
@@ -247,6 +786,78 @@ pub struct BowConfig {
     pub intervals: Vec<Decimal>,
     pub fee: Decimal,
     pub amp: Decimal,
+    #[serde(default)]
+    pub bounds: BowBounds,
+}
+
+/// bounds of the BOW market maker curve a launch can choose
+#[cw_serde]
+#[derive(Default)]
+pub struct BowBounds {
+    pub min_interval: Decimal,
+    pub max_interval: Option<Decimal>,
+    pub max_intervals: Option<u8>,
+    pub min_fee: Decimal,
+    pub max_fee: Option<Decimal>,
+    pub min_amp: Decimal,
+    pub max_amp: Option<Decimal>,
+}
+
+/// FIN fees and BOW market maker curve of a launch, unset values use those of the config
+#[cw_serde]
+#[derive(Default)]
+pub struct MarketParams {
+    pub fee_maker: Option<Decimal256>,
+    pub fee_taker: Option<Decimal256>,
+    pub bow_intervals: Option<Vec<Decimal>>,
+    pub bow_fee: Option<Decimal>,
+    pub bow_amp: Option<Decimal>,
+    pub liquidity_pricing: Option<LiquidityPricing>,
+    pub leftover: Option<LiquidityLeftover>,
+}
+
+/// price the initial liquidity of each market is seeded at
+#[cw_serde]
+#[derive(Default)]
+pub enum LiquidityPricing {
+    /// average price of the raise of the market
+    #[default]
+    AveragePrice,
+    /// price of the highest premium slot the sales filled, from the Orca bids snapshot taken when
+    /// the sales were executed, the average price when there is no snapshot
+    ClearingPrice,
+    /// the average price unless it is below this price, in whole bid denoms per whole token
+    Floor { price: Decimal },
+}
+
+/// recipient of the bid denom or the tokens left over once the liquidity is seeded at its price
+#[cw_serde]
+#[derive(Default)]
+pub enum LiquidityLeftover {
+    /// the beneficiaries of the sales of the market, in proportion to their raise
+    #[default]
+    Beneficiary,
+    Treasury {
+        address: Addr,
+    },
+}
+
+#[cw_serde]
+pub struct LaunchConfig {
+    pub max_active_per_owner: u32,
+    /// paid to the sender of KeeperStart out of the prefunded funds of the launch, in the denom of
+    /// the deposit
+    #[serde(default)]
+    pub keeper_reward: Uint128,
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            max_active_per_owner: 1,
+            keeper_reward: Uint128::zero(),
+        }
+    }
 }
 
 #[cw_serde]
@@ -258,12 +869,32 @@ pub struct Config {
     pub flows: FlowsConfig,
     pub fin: FinConfig,
     pub bow: BowConfig,
+    #[serde(default)]
+    pub launch: LaunchConfig,
 }
 
 #[cw_serde]
 pub struct ReplyInfo {
     pub reply_type: ReplyTypes,
     pub idx: Uint128,
+    /// index of the Pilot sale in the launch
+    #[serde(default)]
+    pub sale: u8,
+}
+
+impl ReplyInfo {
+    /// unique reply id for the submessage, the reply type is encoded in the lowest byte,
+    /// the sale in the highest byte and the launch idx in the 48 bits between them
+    /// errors for launch idxs that do not fit so that ids never overlap
+    pub fn id(&self) -> Result<u64, ContractError> {
+        ensure!(
+            self.idx.u128() < 1 << 48,
+            ContractError::ReplyIdOverflow(self.idx.to_string())
+        );
+        Ok(((self.sale as u64) << 56)
+            | ((self.idx.u128() as u64) << 8)
+            | self.reply_type.clone() as u64)
+    }
 }
 
 #[cw_serde]
@@ -272,4 +903,6 @@ pub enum ReplyTypes {
     Execute = 2,
     PostExecute = 3,
     Retract = 4,
+    Bid = 5,
+    Settle = 6,
 }
@@ -0,0 +1,859 @@
+use std::ops::Add;
+
+use cosmwasm_std::{
+    coin, coins, ensure, instantiate2_address, to_json_binary, wasm_execute, Addr, BankMsg, Binary,
+    CodeInfoResponse, Coin, CosmosMsg, Decimal, Decimal256, Deps, Env, SubMsg, Uint128, WasmMsg,
+};
+use cw_utils::NativeBalance;
+use fuzion_flows::{FlowCreate, FlowSchedule, FlowType};
+use fuzion_utilities::{Asset, AssetList, DenomUnit, LogoURIs};
+use kujira::{Denom, DenomMsg, KujiraMsg, KujiraQuery, Precision};
+use kujira_pilot::Status;
+
+use crate::launch::Launch;
+use crate::msg::{
+    CallbackType, CategoryTypes, Config, LaunchStatus, LiquidityDestination, LiquidityLeftover,
+    LiquidityPosition, LiquidityPricing, LiquidityTarget, LpVestingTranche, MarketSimulation,
+    ReplyInfo, ReplyTypes, Tokenomics,
+};
+use crate::pricing;
+use crate::ContractError;
+
+/// Everything PostLaunch sends and the values it is derived from
+pub struct PostLaunchPlan {
+    pub messages: Vec<SubMsg<KujiraMsg>>,
+    pub average_price: Decimal256,
+    pub price_precision_decimals: u8,
+    pub lp_stable_amount: Uint128,
+    pub beneficiary_funds: Coin,
+    pub fin_address: Addr,
+    /// BOW of the first liquidity target
+    pub bow_address: Addr,
+    /// one entry per bid denom raised, the values above are those of the first one
+    pub markets: Vec<MarketSimulation>,
+    pub liquidity: Vec<LiquidityPosition>,
+    pub required_deposit: Vec<Coin>,
+}
+
+/// Sale executed on Pilot and what it contributes to the market of its bid denom
+struct ExecutedSale {
+    beneficiary: Addr,
+    raise_total: Uint128,
+    raise_amount: Uint128,
+    sold: Uint128,
+    /// price of the highest premium slot filled, in bid denom units per token unit
+    clearing_price: Option<Decimal>,
+}
+
+/// Liquidity targets of the launch, a single BOW market maker unless the liquidity category
+/// lists its own targets
+pub fn liquidity_targets(tokenomics: &Tokenomics) -> Vec<LiquidityTarget> {
+    tokenomics
+        .categories
+        .iter()
+        .find(|c| c.category_type == CategoryTypes::Liquidity)
+        .and_then(|c| c.liquidity.clone())
+        .unwrap_or(vec![LiquidityTarget {
+            destination: LiquidityDestination::BowMarketMaker,
+            share: Decimal::one(),
+        }])
+}
+
+/// Vesting tranches of the LP tokens, a single tranche to the Pilot beneficiary with the configured
+/// cliff and duration unless the liquidity category has its own plan
+pub fn lp_vesting(config: &Config, tokenomics: &Tokenomics) -> Vec<LpVestingTranche> {
+    tokenomics
+        .categories
+        .iter()
+        .find(|c| c.category_type == CategoryTypes::Liquidity)
+        .and_then(|c| c.lp_vesting.clone())
+        .unwrap_or(vec![LpVestingTranche {
+            taker: None,
+            share: Decimal::one(),
+            cliff: config.tokenomics.default_lp_vest_cliff,
+            duration: config.tokenomics.default_lp_vest_duration,
+        }])
+}
+
+/// Beneficiaries of the executed sales of a market with their raise, the first Pilot beneficiary for
+/// launches executed before the sale results were recorded
+fn market_beneficiaries(launch: &Launch, bid_denom: &Denom) -> Vec<(Addr, Uint128)> {
+    let beneficiaries: Vec<(Addr, Uint128)> = launch
+        .pilots
+        .iter()
+        .filter(|p| p.orca.bid_denom == *bid_denom)
+        .filter_map(|p| {
+            p.result
+                .as_ref()
+                .map(|r| (p.beneficiary.clone(), r.raise_total))
+        })
+        .collect();
+    if beneficiaries.is_empty() {
+        return vec![(launch.pilots[0].beneficiary.clone(), Uint128::one())];
+    }
+    beneficiaries
+}
+
+/// Flows vesting the LP tokens of a BOW deposit of a market, the last tranche receives the rounding
+/// remainder
+/// Tranches without a taker vest to the beneficiaries of the market's sales in proportion to their
+/// raise, the last beneficiary receives the rounding remainder
+pub fn lp_flows(
+    config: &Config,
+    launch: &Launch,
+    lp: &Coin,
+    bid_denom: &Denom,
+    now: u64,
+) -> Vec<FlowCreate> {
+    let tranches = lp_vesting(config, &launch.clone().tokenomics.unwrap());
+    let beneficiaries = market_beneficiaries(launch, bid_denom);
+    let raised = beneficiaries
+        .iter()
+        .fold(Uint128::zero(), |acc, (_, raise)| acc + *raise);
+    let mut remaining = lp.amount;
+    let mut flows = vec![];
+    for (i, tranche) in tranches.iter().enumerate() {
+        let amount = if i == tranches.len() - 1 {
+            remaining
+        } else {
+            lp.amount * tranche.share
+        };
+        remaining -= amount;
+
+        let takers = match tranche.taker.clone() {
+            Some(taker) => vec![(taker, amount)],
+            None => {
+                let mut remaining_tranche = amount;
+                beneficiaries
+                    .iter()
+                    .enumerate()
+                    .map(|(b, (beneficiary, raise))| {
+                        let share = if b == beneficiaries.len() - 1 {
+                            remaining_tranche
+                        } else {
+                            amount.multiply_ratio(*raise, raised)
+                        };
+                        remaining_tranche -= share;
+                        (beneficiary.clone(), share)
+                    })
+                    .collect()
+            }
+        };
+
+        for (taker, amount) in takers {
+            if amount.is_zero() {
+                continue;
+            }
+
+            flows.push(FlowCreate {
+                flow_type: FlowType::Vesting,
+                taker,
+                denom: lp.denom.clone(),
+                genesis_time: now,
+                identifier: None,
+                schedules: vec![FlowSchedule {
+                    start_time: now,
+                    end_time: now + tranche.duration,
+                    amount,
+                    cliff_end_time: now + tranche.cliff,
+                }],
+            });
+        }
+    }
+    flows
+}
+
+/// Bid denoms of the executed sales, and of the sales of the live round, in the order they were
+/// scheduled, PostLaunch creates a market for each of them
+pub fn market_denoms(launch: &Launch) -> Vec<Denom> {
+    let live = match launch.status {
+        LaunchStatus::SaleLive => launch.round_sales(None).map(|(_, s)| s).unwrap_or_default(),
+        _ => vec![],
+    };
+    let mut denoms: Vec<Denom> = vec![];
+    for (_, pilot) in launch
+        .pilots
+        .iter()
+        .enumerate()
+        .filter(|(sale, p)| p.result.is_some() || live.contains(sale))
+    {
+        if !denoms.contains(&pilot.orca.bid_denom) {
+            denoms.push(pilot.orca.bid_denom.clone());
+        }
+    }
+    if denoms.is_empty() {
+        denoms.extend(launch.pilots.first().map(|p| p.orca.bid_denom.clone()));
+    }
+    denoms
+}
+
+/// Denom fee paid for the LP denom of every BOW contract created by PostLaunch
+pub fn bow_creation_fee(config: &Config, launch: &Launch) -> Coin {
+    let created = liquidity_targets(&launch.clone().tokenomics.unwrap())
+        .iter()
+        .filter(|t| !matches!(t.destination, LiquidityDestination::ExistingFin { .. }))
+        .count()
+        * market_denoms(launch).len();
+    coin(
+        config.token.denom_fee.amount.u128() * created as u128,
+        config.token.denom_fee.denom.clone(),
+    )
+}
+
+/// Funds the owner has to send with PostLaunch
+/// Non managed tokens also deposit every non sale allocation
+pub fn required_deposit(config: &Config, launch: &Launch) -> Vec<Coin> {
+    let mut deposit = NativeBalance(vec![bow_creation_fee(config, launch)]);
+    let token = launch.clone().token.unwrap();
+    if !token.is_managed {
+        let mut non_managed_amount = Uint128::zero();
+        for category in launch.clone().tokenomics.unwrap().categories {
+            if category.category_type != CategoryTypes::Sale {
+                for recipient in category.recipients {
+                    non_managed_amount = non_managed_amount.add(recipient.amount);
+                }
+            }
+        }
+        deposit = deposit + coin(non_managed_amount.u128(), token.denom.to_string());
+    }
+    deposit.normalize();
+    deposit.into_vec()
+}
+
+/// Builds the PostLaunch messages for the executed Pilot sales
+/// Sets up the vesting schedules, a FIN pair and its BOW contracts for every bid denom raised, the
+/// BOW deposits, the denom admin change, the utilities uploads and the beneficiary payouts
+pub fn plan(
+    deps: Deps<KujiraQuery>,
+    env: &Env,
+    config: &Config,
+    launch: &Launch,
+) -> Result<PostLaunchPlan, ContractError> {
+    let idx = launch.idx;
+
+    let pilot_config: kujira_pilot::ConfigResponse = deps.querier.query_wasm_smart(
+        config.pilot.pilot_contract.clone(),
+        &kujira_pilot::QueryMsg::Config {},
+    )?;
+
+    // Only allow launches whose started sales are all filled or retracted to execute this message
+    // The executed sales are grouped by their bid denom, each one gets its own market
+    let tokenomics = launch.clone().tokenomics.unwrap();
+    let allocations = tokenomics.sale_allocations();
+    let token_decimals = launch.clone().token.unwrap().decimals;
+    let mut sold_total = Uint128::zero();
+    let mut markets: Vec<(Denom, Vec<ExecutedSale>)> = vec![];
+    for (sale, pilot) in launch.pilots.iter().enumerate() {
+        if let Some(sale_idx) = pilot.idx {
+            let pilot_sale: kujira_pilot::SaleResponse = deps.querier.query_wasm_smart(
+                config.pilot.pilot_contract.clone(),
+                &kujira_pilot::QueryMsg::Sale { idx: sale_idx },
+            )?;
+            match pilot_sale.status {
+                Status::Executed {
+                    raise_total,
+                    raise_amount,
+                    ..
+                } => {
+                    let bid_decimals = config
+                        .pilot
+                        .allowed_bid_denoms
+                        .iter()
+                        .find(|d| d.denom == pilot.orca.bid_denom)
+                        .map_or(token_decimals, |d| d.decimals);
+                    let price = pricing::base_price(
+                        pilot.sale.price,
+                        pricing::decimal_delta(token_decimals, bid_decimals),
+                    );
+                    let bid_pools = launch.bid_pools(deps.storage, sale)?;
+                    let executed = ExecutedSale {
+                        beneficiary: pilot.beneficiary.clone(),
+                        raise_total,
+                        raise_amount,
+                        sold: allocations[sale].1,
+                        clearing_price: bid_pools.as_ref().and_then(|bid_pools| {
+                            pricing::clearing_price(price, bid_pools, allocations[sale].1)
+                        }),
+                    };
+                    sold_total += executed.sold;
+                    match markets
+                        .iter_mut()
+                        .find(|(denom, _)| *denom == pilot.orca.bid_denom)
+                    {
+                        Some((_, sales)) => sales.push(executed),
+                        None => markets.push((pilot.orca.bid_denom.clone(), vec![executed])),
+                    }
+                }
+                Status::Retracted { .. } => {}
+                _ => return Err(ContractError::Unauthorized {}),
+            }
+        }
+    }
+    ensure!(!markets.is_empty(), ContractError::Unauthorized {});
+
+    let mut messages = vec![];
+
+    let denom = launch.clone().token.unwrap().denom;
+    let denom_symbol = launch.clone().token.unwrap().symbol;
+
+    // Setup categories of tokenomcs and vesting schedules
+    for category in tokenomics.categories.clone() {
+        if category.category_type == CategoryTypes::Standard {
+            for recipient in category.recipients {
+                if let Some(flows) = recipient.flows {
+                    if launch.clone().token.unwrap().is_managed {
+                        messages.push(SubMsg::new(CosmosMsg::Custom(KujiraMsg::Denom(
+                            DenomMsg::Mint {
+                                denom: denom.clone(),
+                                amount: recipient.amount,
+                                recipient: env.contract.address.clone(),
+                            },
+                        ))));
+                    };
+                    for flow in flows {
+                        let mut flows_amount = 0u128;
+                        for schedule in flow.clone().schedules {
+                            flows_amount = flows_amount.add(schedule.amount.u128());
+                        }
+                        messages.push(SubMsg::new(CosmosMsg::Wasm(wasm_execute(
+                            &config.flows.flows_contract,
+                            &fuzion_flows::ExecuteMsg::CreateFlows {
+                                flow_list: vec![flow],
+                            },
+                            coins(flows_amount, denom.to_string()),
+                        )?)));
+                    }
+                } else if launch.clone().token.unwrap().is_managed {
+                    messages.push(SubMsg::new(CosmosMsg::Custom(KujiraMsg::Denom(
+                        DenomMsg::Mint {
+                            denom: denom.clone(),
+                            amount: recipient.amount,
+                            recipient: recipient.address.unwrap(),
+                        },
+                    ))));
+                } else {
+                    messages.push(SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                        amount: coins(recipient.amount.u128(), denom.clone().to_string()),
+                        to_address: recipient.address.unwrap().to_string(),
+                    })));
+                }
+            }
+        }
+    }
+
+    // get the Liquidity Tokenomics category
+    let lp_category = tokenomics
+        .categories
+        .iter()
+        .find(|c| c.category_type == CategoryTypes::Liquidity)
+        .unwrap();
+    // Only the share of the liquidity allocation of the sold tokens is deployed, the share of
+    // retracted or unsold sales is not minted or returned to the owner
+    let sale_total = allocations
+        .iter()
+        .fold(Uint128::zero(), |acc, (_, amount)| acc + *amount);
+    let lp_allocation = lp_category.recipients[0].amount;
+    let lp_amount = lp_allocation.multiply_ratio(sold_total, sale_total);
+    let unused_lp = lp_allocation - lp_amount;
+
+    // calculate the LP to provide to the pools
+    if launch.clone().token.unwrap().is_managed {
+        messages.push(SubMsg::new(CosmosMsg::Custom(KujiraMsg::Denom(
+            DenomMsg::Mint {
+                denom: denom.clone(),
+                amount: lp_amount,
+                recipient: env.contract.address.clone(),
+            },
+        ))));
+    } else if !unused_lp.is_zero() {
+        messages.push(SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: launch.owner.to_string(),
+            amount: coins(unused_lp.u128(), denom.to_string()),
+        })));
+    };
+
+    let targets = liquidity_targets(&tokenomics);
+    let market_params = launch.market_params.clone().unwrap_or_default();
+    let mut created = 0u64;
+    let mut remaining_lp = lp_amount;
+
+    let mut liquidity = vec![];
+    let mut simulations = vec![];
+    for (m, (bid_denom, sales)) in markets.iter().enumerate() {
+        let bid_denom_config = config
+            .pilot
+            .allowed_bid_denoms
+            .iter()
+            .find(|d| d.denom == *bid_denom)
+            .unwrap();
+        let raise_total = sales
+            .iter()
+            .fold(Uint128::zero(), |acc, s| acc + s.raise_total);
+        let raise_amount = sales
+            .iter()
+            .fold(Uint128::zero(), |acc, s| acc + s.raise_amount);
+        let sold_amount = sales.iter().fold(Uint128::zero(), |acc, s| acc + s.sold);
+
+        // Setup FIN Pair Contract, unless the launch trades on an existing pair
+        let (fin_address, existing) = market_fin_address(deps, env, config, launch, m, bid_denom)?;
+
+        let decimal_delta = pricing::decimal_delta(
+            launch.clone().token.unwrap().decimals,
+            bid_denom_config.decimals,
+        );
+        let average_price_of_launch =
+            pricing::average_price(raise_amount, sold_amount, decimal_delta);
+
+        // The owner can override the precision the config policy derives from the price
+        let price_precision_decimals = launch.price_precision.unwrap_or_else(|| {
+            pricing::price_precision(&config.fin.price_precision, average_price_of_launch)
+        });
+
+        if !existing {
+            let fin = CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+                admin: Some(config.fin.admin.clone().to_string()),
+                code_id: config.fin.code_id,
+                msg: to_json_binary(&kujira_fin::InstantiateMsg {
+                    owner: env.contract.address.clone(),
+                    denoms: [
+                        cw20::Denom::Native(denom.to_string()),
+                        cw20::Denom::Native(bid_denom.to_string()),
+                    ],
+                    decimal_delta: Some(decimal_delta),
+                    price_precision: Precision::DecimalPlaces(price_precision_decimals),
+                    fee_maker: market_params.fee_maker.unwrap_or(config.fin.fee_maker),
+                    fee_taker: market_params.fee_taker.unwrap_or(config.fin.fee_taker),
+                    fee_address: config.fin.fee_address.clone(),
+                })?,
+                funds: vec![],
+                label: format!("FIN {}-{}", denom_symbol, bid_denom_config.symbol),
+                salt: fin_salt(idx, m as u64),
+            });
+
+            let reply = ReplyInfo {
+                reply_type: ReplyTypes::PostExecute,
+                idx,
+                sale: m as u8,
+            };
+            messages.push(SubMsg::reply_on_success(fin, reply.id()?));
+        }
+
+        // Setup the BOW contracts of the liquidity targets, an existing BOW pool only serves the
+        // single market of its launch
+        let mut bow_addresses = vec![];
+        for target in targets.iter() {
+            let (intervals, fee, amp) = match target.destination.clone() {
+                LiquidityDestination::ExistingFin { fin, bow } => {
+                    ensure!(
+                        markets.len() == 1,
+                        ContractError::InvalidExistingFin(fin.to_string())
+                    );
+                    bow_addresses.push(bow);
+                    continue;
+                }
+                LiquidityDestination::BowMarketMaker => (
+                    market_params
+                        .bow_intervals
+                        .clone()
+                        .unwrap_or_else(|| config.bow.intervals.clone()),
+                    market_params.bow_fee.unwrap_or(config.bow.fee),
+                    market_params.bow_amp.unwrap_or(config.bow.amp),
+                ),
+                LiquidityDestination::BowStable {
+                    intervals,
+                    fee,
+                    amp,
+                } => (intervals, fee, amp),
+            };
+
+            let bow = CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+                admin: Some(config.bow.admin.clone().to_string()),
+                code_id: config.bow.code_id,
+                msg: to_json_binary(&kujira::bow::market_maker::InstantiateMsg {
+                    owner: config.bow.owner.clone(),
+                    fin_contract: fin_address.clone(),
+                    intervals,
+                    fee,
+                    amp,
+                })?,
+                funds: vec![config.token.denom_fee.clone()],
+                label: format!("Bow: {}-{}", denom_symbol, bid_denom_config.symbol),
+                salt: bow_salt(idx, created),
+            });
+
+            messages.push(SubMsg::new(bow));
+            bow_addresses.push(predicted_bow_address(deps, env, config, idx, created)?);
+            created += 1;
+        }
+
+        // Each market receives the liquidity allocation in proportion to the tokens its sales
+        // sold, paired at the price they raised so it stays within the raise, the last market
+        // receives the rounding remainder
+        let market_lp = if m == markets.len() - 1 {
+            remaining_lp
+        } else {
+            lp_amount.multiply_ratio(sold_amount, sold_total)
+        };
+        remaining_lp -= market_lp;
+        let average_stable = pricing::quote_amount(market_lp, raise_total, sold_amount);
+
+        // The liquidity is seeded at the price of the pricing mode of the launch, the bid denom or
+        // the tokens left over from the average price go to the beneficiaries or the treasury
+        let seed_price = match market_params.liquidity_pricing.clone().unwrap_or_default() {
+            LiquidityPricing::AveragePrice => None,
+            LiquidityPricing::ClearingPrice => sales.iter().filter_map(|s| s.clearing_price).min(),
+            LiquidityPricing::Floor { price } => Some(pricing::base_price(price, decimal_delta))
+                .filter(|floor| market_lp * *floor > average_stable),
+        };
+        let (lp_token_amount, lp_stable_amount) =
+            pricing::seed_amounts(market_lp, average_stable, seed_price);
+        let leftover_tokens = market_lp - lp_token_amount;
+        let leftover_stable = average_stable - lp_stable_amount;
+
+        // The beneficiaries pay the liquidity at the average price when the treasury receives the
+        // leftover
+        let (beneficiary_stable, beneficiary_tokens) =
+            match market_params.leftover.clone().unwrap_or_default() {
+                LiquidityLeftover::Beneficiary => (lp_stable_amount, leftover_tokens),
+                LiquidityLeftover::Treasury { address } => {
+                    let mut leftover = NativeBalance(vec![
+                        coin(leftover_tokens.u128(), denom.to_string()),
+                        coin(leftover_stable.u128(), bid_denom.to_string()),
+                    ]);
+                    leftover.normalize();
+                    if !leftover.is_empty() {
+                        messages.push(SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                            to_address: address.to_string(),
+                            amount: leftover.into_vec(),
+                        })));
+                    }
+                    (average_stable, Uint128::zero())
+                }
+            };
+
+        // Provide liquidity to the BOW pools, the last target receives the rounding remainder
+        let mut remaining_denom = lp_token_amount;
+        let mut remaining_stable = lp_stable_amount;
+        for (i, (target, bow_address)) in targets.iter().zip(bow_addresses).enumerate() {
+            let (denom_amount, stable_amount) = if i == targets.len() - 1 {
+                (remaining_denom, remaining_stable)
+            } else {
+                (
+                    lp_token_amount * target.share,
+                    lp_stable_amount * target.share,
+                )
+            };
+            remaining_denom -= denom_amount;
+            remaining_stable -= stable_amount;
+
+            let mut lp_funds = NativeBalance(vec![
+                coin(denom_amount.u128(), denom.to_string()),
+                coin(stable_amount.u128(), bid_denom.to_string()),
+            ]);
+            lp_funds.normalize();
+            let lp_funds = lp_funds.into_vec();
+            let deposit = CosmosMsg::Wasm(wasm_execute(
+                bow_address.clone(),
+                &kujira::bow::market_maker::execute::ExecuteMsg::Deposit {
+                    max_slippage: None,
+                    callback: Some(to_json_binary(&CallbackType::BowCallback { idx })?.into()),
+                },
+                lp_funds.clone(),
+            )?);
+
+            messages.push(SubMsg::new(deposit));
+            liquidity.push(LiquidityPosition {
+                destination: target.destination.clone(),
+                bow: bow_address,
+                deposit: lp_funds,
+                lp: None,
+            });
+        }
+
+        // Each sale pays the liquidity and receives the leftover tokens in proportion to its raise,
+        // the last sale of the market receives the rounding remainder
+        let mut remaining_lp_stable = beneficiary_stable;
+        let mut remaining_tokens = beneficiary_tokens;
+        for (i, sale) in sales.iter().enumerate() {
+            let (sale_lp_stable, sale_tokens) = if i == sales.len() - 1 {
+                (remaining_lp_stable, remaining_tokens)
+            } else {
+                (
+                    beneficiary_stable.multiply_ratio(sale.raise_total, raise_total),
+                    beneficiary_tokens.multiply_ratio(sale.raise_total, raise_total),
+                )
+            };
+            remaining_lp_stable -= sale_lp_stable;
+            remaining_tokens -= sale_tokens;
+            let sale_payout = sale.raise_amount.checked_sub(sale_lp_stable).map_err(|_| {
+                ContractError::LiquidityExceedsRaise(
+                    sale_lp_stable.to_string(),
+                    sale.raise_amount.to_string(),
+                )
+            })?;
+
+            let mut sale_funds = NativeBalance(vec![
+                coin(sale_payout.u128(), bid_denom.to_string()),
+                coin(sale_tokens.u128(), denom.to_string()),
+            ]);
+            sale_funds.normalize();
+            if !sale_funds.is_empty() {
+                messages.push(SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: sale.beneficiary.to_string(),
+                    amount: sale_funds.into_vec(),
+                })));
+            }
+        }
+
+        simulations.push(MarketSimulation {
+            bid_denom: bid_denom.clone(),
+            fin_address,
+            average_price: average_price_of_launch,
+            price_precision_decimals,
+            lp_stable_amount,
+            lp_token_amount,
+            beneficiary_funds: coin(
+                raise_amount
+                    .checked_sub(beneficiary_stable)
+                    .map_err(|_| {
+                        ContractError::LiquidityExceedsRaise(
+                            beneficiary_stable.to_string(),
+                            raise_amount.to_string(),
+                        )
+                    })?
+                    .u128(),
+                bid_denom.to_string(),
+            ),
+        });
+    }
+
+    if launch.clone().token.unwrap().is_managed {
+        let denom_admin = if let Some(denom_admin) = launch.clone().token.unwrap().denom_admin {
+            denom_admin
+        } else {
+            config.token.default_admin.clone()
+        };
+        // Change the denom admin
+        messages.push(SubMsg::new(CosmosMsg::Custom(KujiraMsg::Denom(
+            DenomMsg::ChangeAdmin {
+                denom,
+                address: denom_admin,
+            },
+        ))));
+
+        // Register the token in Fuzion Products
+        let managed_token = launch.clone().token.unwrap();
+        let logo_uris: Option<LogoURIs> = Some(LogoURIs {
+            png: managed_token.png_url,
+            svg: managed_token.svg_url,
+        });
+        let asset = AssetList {
+            chain_name: "kujira".to_string(),
+            assets: vec![Asset {
+                description: Some(format!("{} Token", managed_token.symbol).to_string()),
+                denom_units: vec![
+                    DenomUnit {
+                        denom: managed_token.denom.to_string(),
+                        exponent: 0,
+                    },
+                    DenomUnit {
+                        denom: managed_token.symbol.to_string().to_lowercase(),
+                        exponent: managed_token.decimals as u16,
+                    },
+                ],
+                base: managed_token.denom.to_string(),
+                name: managed_token.symbol.to_string(),
+                display: managed_token.symbol.to_string().to_lowercase(),
+                symbol: managed_token.symbol.to_string(),
+                coingecko_id: None,
+                logo_uris,
+            }],
+        };
+
+        messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.token.utilities_contract.to_string(),
+            msg: to_json_binary(&fuzion_utilities::ExecuteMsg::UploadAsset { asset })?,
+            funds: vec![],
+        })));
+
+        let coin = Coin {
+            denom: managed_token.denom.to_string(),
+            amount: Uint128::from(0_u128),
+        };
+
+        messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.token.utilities_contract.to_string(),
+            msg: to_json_binary(&fuzion_utilities::ExecuteMsg::UploadTotalSupply {
+                total_supply_list: vec![coin.clone()],
+            })?,
+            funds: vec![],
+        })));
+
+        messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.token.utilities_contract.to_string(),
+            msg: to_json_binary(&fuzion_utilities::ExecuteMsg::UploadCuratedDenoms {
+                curated_denom_list: vec![coin],
+            })?,
+            funds: vec![],
+        })));
+    }
+
+    let deposit_refund = pilot_config.deposit.clone();
+    if !deposit_refund.amount.is_zero() {
+        messages.push(SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: launch.owner.to_string(),
+            amount: vec![deposit_refund],
+        })));
+    }
+
+    let first = simulations[0].clone();
+    Ok(PostLaunchPlan {
+        messages,
+        average_price: first.average_price,
+        price_precision_decimals: first.price_precision_decimals,
+        lp_stable_amount: first.lp_stable_amount,
+        beneficiary_funds: first.beneficiary_funds,
+        fin_address: first.fin_address,
+        bow_address: liquidity[0].bow.clone(),
+        markets: simulations,
+        liquidity,
+        required_deposit: required_deposit(config, launch),
+    })
+}
+
+/// the first FIN keeps the salt of single market launches
+pub fn fin_salt(idx: Uint128, n: u64) -> Binary {
+    if n == 0 {
+        Binary::from(format!("keiko_fin_{idx}").as_bytes())
+    } else {
+        Binary::from(format!("keiko_fin_{idx}_{n}").as_bytes())
+    }
+}
+
+/// the first BOW keeps the salt of single BOW launches
+pub fn bow_salt(idx: Uint128, n: u64) -> Binary {
+    if n == 0 {
+        Binary::from(format!("keiko_bow_{idx}").as_bytes())
+    } else {
+        Binary::from(format!("keiko_bow_{idx}_{n}").as_bytes())
+    }
+}
+
+/// FIN and BOW addresses of the launch, derived with instantiate2 from the configured code ids
+/// Launches on an existing FIN pair keep its address, and an existing BOW pool of the first
+/// liquidity target is returned as is
+pub fn predicted_addresses(
+    deps: Deps<KujiraQuery>,
+    env: &Env,
+    config: &Config,
+    launch: &Launch,
+) -> Result<(Addr, Addr), ContractError> {
+    let idx = launch.idx;
+    // the BOW of the first liquidity target, which is the first one created unless it exists
+    let first_target = launch
+        .tokenomics
+        .as_ref()
+        .map(liquidity_targets)
+        .and_then(|targets| targets.into_iter().next());
+    let bow_address = match first_target.map(|t| t.destination) {
+        Some(LiquidityDestination::ExistingFin { bow, .. }) => bow,
+        _ => predicted_bow_address(deps, env, config, idx, 0)?,
+    };
+    if let Some(existing_fin) = launch.existing_fin.clone() {
+        return Ok((existing_fin, bow_address));
+    }
+
+    let fin_address = predicted_fin_address(deps, env, config, idx, 0)?;
+
+    Ok((fin_address, bow_address))
+}
+
+/// FIN pair of the nth market and whether it already exists, the existing FIN pair of the launch
+/// trades the bid denom of its first sale
+pub fn market_fin_address(
+    deps: Deps<KujiraQuery>,
+    env: &Env,
+    config: &Config,
+    launch: &Launch,
+    n: usize,
+    bid_denom: &Denom,
+) -> Result<(Addr, bool), ContractError> {
+    if let Some(existing_fin) = launch.existing_fin.clone() {
+        if launch.pilots[0].orca.bid_denom == *bid_denom {
+            return Ok((existing_fin, true));
+        }
+    }
+    Ok((
+        predicted_fin_address(deps, env, config, launch.idx, n as u64)?,
+        false,
+    ))
+}
+
+/// address of the FIN contract created for the nth market of the launch
+pub fn predicted_fin_address(
+    deps: Deps<KujiraQuery>,
+    env: &Env,
+    config: &Config,
+    idx: Uint128,
+    n: u64,
+) -> Result<Addr, ContractError> {
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+
+    let CodeInfoResponse { checksum, .. } =
+        deps.querier.query_wasm_code_info(config.fin.code_id)?;
+    Ok(deps
+        .api
+        .addr_humanize(&instantiate2_address(&checksum, &creator, &fin_salt(idx, n)).unwrap())?)
+}
+
+/// Ensures the FIN contract trades the launch token against the bid denom, and that the existing
+/// BOW pool of a liquidity target market makes on it
+pub fn validate_existing_fin(
+    deps: Deps<KujiraQuery>,
+    fin: &Addr,
+    bow: Option<&Addr>,
+    denom: &Denom,
+    bid_denom: &Denom,
+) -> Result<(), ContractError> {
+    deps.api.addr_validate(fin.as_str())?;
+    let fin_config: kujira_fin::ConfigResponse = deps
+        .querier
+        .query_wasm_smart(fin, &kujira_fin::QueryMsg::Config {})
+        .map_err(|_| ContractError::InvalidExistingFin(fin.to_string()))?;
+
+    ensure!(
+        fin_config.denoms.contains(denom) && fin_config.denoms.contains(bid_denom),
+        ContractError::InvalidExistingFin(fin.to_string())
+    );
+
+    // an existing BOW pool has to market make on the FIN pair
+    if let Some(bow) = bow {
+        deps.api.addr_validate(bow.as_str())?;
+        let bow_config: kujira::bow::market_maker::query::ConfigResponse = deps
+            .querier
+            .query_wasm_smart(bow, &kujira::bow::market_maker::query::QueryMsg::Config {})
+            .map_err(|_| ContractError::InvalidExistingFin(bow.to_string()))?;
+        ensure!(
+            bow_config.fin_contract == *fin,
+            ContractError::InvalidExistingFin(bow.to_string())
+        );
+    }
+    Ok(())
+}
+
+/// address of the nth BOW contract created for the launch
+pub fn predicted_bow_address(
+    deps: Deps<KujiraQuery>,
+    env: &Env,
+    config: &Config,
+    idx: Uint128,
+    n: u64,
+) -> Result<Addr, ContractError> {
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+
+    let CodeInfoResponse { checksum, .. } =
+        deps.querier.query_wasm_code_info(config.bow.code_id)?;
+    Ok(deps
+        .api
+        .addr_humanize(&instantiate2_address(&checksum, &creator, &bow_salt(idx, n)).unwrap())?)
+}
@@ -0,0 +1,166 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Decimal, Decimal256, Uint128, Uint256};
+use kujira_orca::BidPoolsResponse;
+
+use crate::msg::PricePrecisionPolicy;
+
+/// Decimals the token has over the bid denom, negative when the bid denom has more decimals
+/// This is the decimal delta of the FIN pair of the token against the bid denom
+pub fn decimal_delta(token_decimals: u8, bid_decimals: u8) -> i8 {
+    (token_decimals as i16 - bid_decimals as i16) as i8
+}
+
+fn scale(delta: i8) -> Uint256 {
+    Uint256::from(10u8).pow(delta.unsigned_abs() as u32)
+}
+
+/// Price in bid denom units per token unit of a price in whole bid denoms per whole token, as
+/// Pilot sales are created with
+pub fn base_price(price: Decimal, delta: i8) -> Decimal {
+    let scale = Decimal::from_str("10")
+        .unwrap()
+        .pow(delta.unsigned_abs() as u32);
+    if delta >= 0 {
+        price / scale
+    } else {
+        price * scale
+    }
+}
+
+/// Price in whole bid denoms per whole token of an amount raised in bid denom units for an
+/// amount sold in token units
+pub fn average_price(raised: Uint128, sold: Uint128, delta: i8) -> Decimal256 {
+    if delta >= 0 {
+        Decimal256::from_ratio(Uint256::from(raised) * scale(delta), sold)
+    } else {
+        Decimal256::from_ratio(raised, Uint256::from(sold) * scale(delta))
+    }
+}
+
+/// Bid denom units that pair an amount of token units at the price of a raise, the ratio is taken
+/// in units so it does not depend on the decimals
+pub fn quote_amount(amount: Uint128, raised: Uint128, sold: Uint128) -> Uint128 {
+    raised.multiply_ratio(amount, sold)
+}
+
+/// Tokens and bid denom units seeding the liquidity of an allocation at a price, when the bid denom
+/// of the average price does not cover the allocation at the price fewer tokens are seeded
+pub fn seed_amounts(tokens: Uint128, quote: Uint128, price: Option<Decimal>) -> (Uint128, Uint128) {
+    match price {
+        None => (tokens, quote),
+        Some(price) => {
+            let at_price = tokens * price;
+            if at_price <= quote {
+                (tokens, at_price)
+            } else {
+                (
+                    quote.multiply_ratio(Decimal::one().atomics(), price.atomics()),
+                    quote,
+                )
+            }
+        }
+    }
+}
+
+/// Orca slot of a premium rate, rounded to the nearest slot as the rate of a pool may not be an
+/// exact multiple of the rate per slot, None when the rate per slot is zero
+pub fn premium_slot(premium_rate: Decimal, premium_rate_per_slot: Decimal) -> Option<u8> {
+    premium_rate
+        .checked_div(premium_rate_per_slot)
+        .ok()
+        .map(|slot| (slot + Decimal::percent(50)).to_uint_floor().u128() as u8)
+}
+
+/// Bids of an Orca slot and the part of them a sale filled
+pub struct SlotFill {
+    pub premium_rate: Decimal,
+    /// price in bid denom units per token unit the bids of the slot buy at
+    pub price: Decimal,
+    pub bid_amount: Uint128,
+    /// bid denom units of the bids that bought tokens
+    pub filled_amount: Uint128,
+    /// token units bought by the bids of the slot
+    pub tokens: Uint128,
+}
+
+/// Fills of the slots by a sale of an amount at a price in bid denom units per token unit, in
+/// ascending premium order as Orca fills the slots from the lowest premium
+pub fn slot_fills(price: Decimal, bid_pools: &BidPoolsResponse, sold: Uint128) -> Vec<SlotFill> {
+    let mut pools = bid_pools.bid_pools.clone();
+    pools.sort_by(|a, b| a.premium_rate.cmp(&b.premium_rate));
+
+    let mut remaining = sold;
+    pools
+        .into_iter()
+        .map(|pool| {
+            let slot_price = price * (Decimal::one() - pool.premium_rate);
+            let mut fill = SlotFill {
+                premium_rate: pool.premium_rate,
+                price: slot_price,
+                bid_amount: pool.total_bid_amount,
+                filled_amount: Uint128::zero(),
+                tokens: Uint128::zero(),
+            };
+            if !slot_price.is_zero() && !remaining.is_zero() {
+                let tokens = pool
+                    .total_bid_amount
+                    .multiply_ratio(Decimal::one().atomics(), slot_price.atomics());
+                if tokens >= remaining {
+                    fill.tokens = remaining;
+                    fill.filled_amount = remaining * slot_price;
+                } else {
+                    fill.tokens = tokens;
+                    fill.filled_amount = pool.total_bid_amount;
+                }
+                remaining -= fill.tokens;
+            }
+            fill
+        })
+        .collect()
+}
+
+/// Highest premium slot that filled a sale of an amount, None when the bids do not cover it
+pub fn clearing_slot(fills: &[SlotFill], sold: Uint128) -> Option<&SlotFill> {
+    let bought = fills.iter().fold(Uint128::zero(), |acc, f| acc + f.tokens);
+    if bought < sold {
+        return None;
+    }
+    fills.iter().rev().find(|f| !f.tokens.is_zero())
+}
+
+/// Price in bid denom units per token unit of the highest premium slot a sale of an amount fills
+/// None when the bids do not cover the amount
+pub fn clearing_price(
+    price: Decimal,
+    bid_pools: &BidPoolsResponse,
+    sold: Uint128,
+) -> Option<Decimal> {
+    clearing_slot(&slot_fills(price, bid_pools, sold), sold).map(|slot| slot.price)
+}
+
+/// Decimal places of the FIN price of a pair trading at the price, so that prices keep the
+/// significant digits of the policy, within its decimal places bounds
+pub fn price_precision(policy: &PricePrecisionPolicy, price: Decimal256) -> u8 {
+    if price.is_zero() {
+        return policy.max_decimal_places;
+    }
+
+    // the exponent of the leading digit of the price
+    let ten = Decimal256::from_ratio(10u8, 1u8);
+    let mut exponent = 0i32;
+    let mut scaled = price;
+    while scaled >= ten {
+        scaled /= ten;
+        exponent += 1;
+    }
+    while scaled < Decimal256::one() {
+        scaled *= ten;
+        exponent -= 1;
+    }
+
+    (policy.significant_digits as i32 - 1 - exponent).clamp(
+        policy.min_decimal_places as i32,
+        policy.max_decimal_places as i32,
+    ) as u8
+}
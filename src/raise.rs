@@ -0,0 +1,49 @@
+use std::ops::Mul;
+
+use cosmwasm_std::{Decimal, Uint128};
+use kujira_pilot::CreateOrca;
+
+use crate::msg::RaiseEstimate;
+use crate::pricing;
+use crate::ContractError;
+
+/// Premium of an Orca slot, the discount on the sale price of the bids placed in it
+pub fn slot_premium(orca: &CreateOrca, slot: u8) -> Result<Decimal, ContractError> {
+    orca.premium_rate_per_slot
+        .checked_mul(Decimal::from_ratio(slot, 1u8))
+        .ok()
+        .filter(|premium| *premium < Decimal::one())
+        .ok_or_else(|| ContractError::InvalidPremiumRate(orca.premium_rate_per_slot.to_string()))
+}
+
+/// Raise of a sale when every token is sold at the premium of the slot
+pub fn slot_raise(
+    amount: Uint128,
+    price: Decimal,
+    orca: &CreateOrca,
+    slot: u8,
+) -> Result<Uint128, ContractError> {
+    let premium = slot_premium(orca, slot)?;
+    Ok(amount.mul(price.mul(Decimal::one() - premium)))
+}
+
+/// Range of the raise of a sale that sells out, the minimum sells every token at the premium of
+/// the highest slot and the maximum sells every token at the sale price
+/// The price is the one of the sale, it is scaled by the decimal delta like PilotStart does
+pub fn estimate(
+    amount: Uint128,
+    price: Decimal,
+    token_decimals: u8,
+    bid_decimals: u8,
+    orca: &CreateOrca,
+    sale_fee: Decimal,
+) -> Result<RaiseEstimate, ContractError> {
+    let price = pricing::base_price(price, pricing::decimal_delta(token_decimals, bid_decimals));
+    let min_raise = slot_raise(amount, price, orca, orca.max_slot)?;
+    let max_raise = slot_raise(amount, price, orca, 0)?;
+    Ok(RaiseEstimate {
+        min_raise,
+        max_raise,
+        net_min_raise: min_raise.mul(Decimal::one() - sale_fee),
+    })
+}
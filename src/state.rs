@@ -1,12 +1,36 @@
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, UniqueIndex};
+use cosmwasm_std::{Addr, Coin};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
+use kujira_orca::BidPoolsResponse;
 
 use crate::{
     launch::Launch,
-    msg::{Config, ReplyInfo},
+    msg::{AllowlistBid, Config, LaunchHistoryEntry, ReplyInfo},
 };
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const REPLY: Item<ReplyInfo> = Item::new("reply");
+/// pending submessage replies keyed by reply id, see `ReplyInfo::id`
+pub const REPLIES: Map<u64, ReplyInfo> = Map::new("replies");
+
+/// first launch idx of the next batch of legacy launches to migrate, removed once every launch
+/// is migrated
+pub const MIGRATION_CURSOR: Item<u128> = Item::new("migration_cursor");
+
+/// append-only history of every action taken on a launch, keyed by launch idx and sequence
+/// the config arms UpdateConfig, SetContractAdmin and ExecuteContract act on no launch and write
+/// no entry
+pub const LAUNCH_HISTORY: Map<(u128, u64), LaunchHistoryEntry> = Map::new("launch_history");
+
+/// launch idx and sale index of every sale created on the pilot contract, keyed by the pilot sale idx
+pub const PILOT_SALES: Map<u128, (u128, u8)> = Map::new("pilot_sales");
+
+/// bids placed through AllowlistBid, keyed by launch idx and bidder
+pub const ALLOWLIST_BIDS: Map<(u128, &Addr), Vec<AllowlistBid>> = Map::new("allowlist_bids");
+
+/// bidder and funds of the bid waiting for the Orca reply, keyed by reply id
+pub const PENDING_BIDS: Map<u64, (Addr, Coin)> = Map::new("pending_bids");
+
+/// every Orca bid pool of a sale snapshotted before its execution, keyed by launch idx and sale index
+pub const BID_POOLS: Map<(u128, u8), BidPoolsResponse> = Map::new("bid_pools");
 
 const LAUNCH_NAMESPACE: &str = "launch";
 
@@ -14,13 +38,20 @@ pub struct LaunchIndexes<'a> {
     pub key: UniqueIndex<'a, u128, Launch>,
     pub owner: MultiIndex<'a, String, Launch, u128>,
     pub status: MultiIndex<'a, String, Launch, u128>,
+    /// owner and status, counts the active launches of an owner without loading them
+    pub owner_status: MultiIndex<'a, (String, String), Launch, u128>,
     pub pilot_idx: MultiIndex<'a, u128, Launch, u128>,
 }
 
 impl<'a> IndexList<Launch> for LaunchIndexes<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Launch>> + '_> {
-        let v: Vec<&dyn Index<Launch>> =
-            vec![&self.key, &self.owner, &self.status, &self.pilot_idx];
+        let v: Vec<&dyn Index<Launch>> = vec![
+            &self.key,
+            &self.owner,
+            &self.status,
+            &self.owner_status,
+            &self.pilot_idx,
+        ];
         Box::new(v.into_iter())
     }
 }
@@ -38,9 +69,14 @@ pub fn launch<'a>() -> IndexedMap<'a, u128, Launch, LaunchIndexes<'a>> {
             LAUNCH_NAMESPACE,
             "launch__status",
         ),
+        owner_status: MultiIndex::new(
+            |_d, d| (d.owner.to_string(), d.status.to_string()),
+            LAUNCH_NAMESPACE,
+            "launch__owner_status",
+        ),
         pilot_idx: MultiIndex::new(
             |_d, d| {
-                if let Some(pilot) = d.clone().pilot {
+                if let Some(pilot) = d.pilots.first() {
                     if let Some(idx) = pilot.idx {
                         idx.u128()
                     } else {
@@ -4,15 +4,20 @@ use crate::{
     contract::{execute, instantiate, query, reply},
     launch::Launch,
     msg::{
-        BidDenoms, BowConfig, CategoryTypes, FinConfig, FlowsConfig, LaunchStatus, PilotConfig,
+        BidDenoms, BowBounds, BowConfig, CategoryTypes, Config, FinConfig, FinFeeBounds,
+        FlowsConfig, LaunchAction, LaunchConfig, LaunchHistoryEntry, LaunchStatus,
+        LiquidityDestination, LiquidityLeftover, LiquidityPricing, LiquidityTarget, LpVestBounds,
+        LpVestingTranche, MarketParams, PilotConfig, PredictedAddressesResponse,
+        PricePrecisionPolicy, RaiseEstimate, SaleAnalytics, SaleBounds, SimulatePostLaunchResponse,
         TokenConfig, Tokenomics, TokenomicsCategories, TokenomicsConfig, TokenomicsRecipient,
     },
+    pricing, raise,
 };
 
 use super::*;
 use cosmwasm_std::{
-    coin, coins, to_json_binary, Addr, Binary, Coin, Decimal, Decimal256, Deps, DepsMut, Env,
-    MessageInfo, Response, StdResult, Timestamp, Uint128,
+    coin, coins, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal,
+    Decimal256, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Timestamp, Uint128, WasmMsg,
 };
 use cw_multi_test::{ContractWrapper, Executor};
 use cw_utils::PaymentError;
@@ -188,6 +193,7 @@ fn launch_new_token() {
                     minimum_liquidity_one_side: Decimal::from_str("0.1").unwrap(),
                     default_lp_vest_cliff: 0,
                     default_lp_vest_duration: 60000,
+                    lp_vest_bounds: LpVestBounds::default(),
                 },
                 pilot: PilotConfig {
                     pilot_contract: pilot_addr.clone(),
@@ -197,6 +203,7 @@ fn launch_new_token() {
                         decimals: 6,
                     }],
                     min_raise_amount: Uint128::from(100_000_000_000u128),
+                    sale_bounds: SaleBounds::default(),
                 },
                 flows: FlowsConfig {
                     flows_contract: Addr::unchecked("flows"),
@@ -208,6 +215,8 @@ fn launch_new_token() {
                     fee_maker: Decimal256::from_str("0.00075").unwrap(),
                     fee_taker: Decimal256::from_str("0.0015").unwrap(),
                     fee_address: Addr::unchecked("fee_address"),
+                    price_precision: PricePrecisionPolicy::default(),
+                    fee_bounds: FinFeeBounds::default(),
                 },
                 bow: BowConfig {
                     code_id: bow_code_id,
@@ -225,6 +234,11 @@ fn launch_new_token() {
                     ],
                     fee: Decimal::from_str("0.001").unwrap(),
                     amp: Decimal::from_str("1").unwrap(),
+                    bounds: BowBounds::default(),
+                },
+                launch: LaunchConfig {
+                    max_active_per_owner: 1,
+                    keeper_reward: Uint128::zero(),
                 },
             },
             &[],
@@ -360,7 +374,7 @@ fn launch_new_token() {
         token_response.svg_url,
         Some("https://example.com/sned.svg".to_string())
     );
-    assert_eq!(launch.status, LaunchStatus::Created);
+    assert_eq!(launch.status, LaunchStatus::TokenSet);
 
     let tokenomics = Tokenomics {
         categories: vec![
@@ -372,6 +386,8 @@ fn launch_new_token() {
                     amount: Uint128::from(1_000_000_000_000u128),
                     flows: None,
                 }],
+                liquidity: None,
+                lp_vesting: None,
             },
             TokenomicsCategories {
                 label: "Liquidity".to_string(),
@@ -381,6 +397,8 @@ fn launch_new_token() {
                     amount: Uint128::from(100_000_000_000u128),
                     flows: None,
                 }],
+                liquidity: None,
+                lp_vesting: None,
             },
         ],
     };
@@ -408,7 +426,7 @@ fn launch_new_token() {
 
     assert!(launch.tokenomics.is_some());
     assert_eq!(launch.tokenomics.unwrap(), tokenomics);
-    assert_eq!(launch.status, LaunchStatus::Created);
+    assert_eq!(launch.status, LaunchStatus::TokenomicsSet);
 
     let pilot_sale = CreateSale {
         title: "SNED".to_string(),
@@ -434,8 +452,12 @@ fn launch_new_token() {
             keiko_addr.clone(),
             &&ExecuteMsg::PilotSchedule {
                 idx: launch.idx,
+                round: None,
+                recipient: None,
                 sale: pilot_sale,
                 orca: create_orca,
+                existing_fin: None,
+                market: None,
             },
             &[],
         )
@@ -474,8 +496,12 @@ fn launch_new_token() {
         keiko_addr.clone(),
         &&ExecuteMsg::PilotSchedule {
             idx: launch.idx,
+            round: None,
+            recipient: None,
             sale: pilot_sale.clone(),
             orca: create_orca.clone(),
+            existing_fin: None,
+            market: None,
         },
         &[],
     )
@@ -491,14 +517,14 @@ fn launch_new_token() {
         )
         .unwrap();
 
-    assert!(launch.pilot.is_some());
-    let pilot = launch.pilot.unwrap();
+    assert!(!launch.pilots.is_empty());
+    let pilot = launch.pilots[0].clone();
     let mut pilot_sale_updated = pilot_sale.clone();
     pilot_sale_updated.beneficiary = keiko_addr.clone();
     assert_eq!(pilot.sale, pilot_sale_updated);
     assert_eq!(pilot.orca, create_orca);
     assert_eq!(pilot.beneficiary, pilot_sale.beneficiary);
-    assert_eq!(launch.status, LaunchStatus::Planned);
+    assert_eq!(launch.status, LaunchStatus::Scheduled);
 
     let mut new_block = app.block_info();
     new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
@@ -507,7 +533,10 @@ fn launch_new_token() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotStart { idx: launch.idx },
+        &&ExecuteMsg::PilotStart {
+            idx: launch.idx,
+            round: None,
+        },
         &[],
     )
     .unwrap();
@@ -522,15 +551,15 @@ fn launch_new_token() {
         )
         .unwrap();
 
-    assert_eq!(launch.clone().pilot.unwrap().idx, Some(Uint128::zero()));
-    assert_eq!(launch.status, LaunchStatus::InProgress);
+    assert_eq!(launch.pilots[0].clone().idx, Some(Uint128::zero()));
+    assert_eq!(launch.status, LaunchStatus::SaleLive);
 
     let pilot_sale: kujira_pilot::SaleResponse = app
         .wrap()
         .query_wasm_smart(
             pilot_addr.clone(),
             &kujira_pilot::QueryMsg::Sale {
-                idx: launch.pilot.unwrap().idx.unwrap(),
+                idx: launch.pilots[0].clone().idx.unwrap(),
             },
         )
         .unwrap();
@@ -568,7 +597,10 @@ fn launch_new_token() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotExecute { idx: launch.idx },
+        &&ExecuteMsg::PilotExecute {
+            idx: launch.idx,
+            round: None,
+        },
         &[],
     )
     .unwrap_err();
@@ -580,7 +612,10 @@ fn launch_new_token() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotExecute { idx: launch.idx },
+        &&ExecuteMsg::PilotExecute {
+            idx: launch.idx,
+            round: None,
+        },
         &[],
     )
     .unwrap();
@@ -595,14 +630,18 @@ fn launch_new_token() {
         )
         .unwrap();
 
-    assert_eq!(launch.status, LaunchStatus::Completed);
+    assert_eq!(launch.status, LaunchStatus::SaleExecuted);
+    assert_eq!(
+        launch.pilots[0].clone().result.unwrap().raise_total,
+        Uint128::from(100_000_000_000u128)
+    );
 
     let pilot_sale: kujira_pilot::SaleResponse = app
         .wrap()
         .query_wasm_smart(
             pilot_addr.clone(),
             &kujira_pilot::QueryMsg::Sale {
-                idx: launch.pilot.unwrap().idx.unwrap(),
+                idx: launch.pilots[0].clone().idx.unwrap(),
             },
         )
         .unwrap();
@@ -618,6 +657,32 @@ fn launch_new_token() {
 
     let _keiko_balances = app.wrap().query_all_balances(keiko_addr.clone()).unwrap();
 
+    let simulation: SimulatePostLaunchResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::SimulatePostLaunch { idx: launch.idx },
+        )
+        .unwrap();
+
+    assert_eq!(simulation.required_deposit, vec![coin(10_000_000, "usk")]);
+    let predicted: PredictedAddressesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::PredictedAddresses { idx: launch.idx },
+        )
+        .unwrap();
+
+    assert_eq!(predicted.fin, simulation.fin_address);
+    assert_eq!(predicted.bow, simulation.bow_address);
+    assert_ne!(predicted.fin, predicted.bow);
+    assert_eq!(
+        simulation.beneficiary_funds.amount,
+        Uint128::from(95_000_000_000u128) - simulation.lp_stable_amount
+    );
+    assert!(!simulation.messages.is_empty());
+
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
@@ -636,9 +701,22 @@ fn launch_new_token() {
         )
         .unwrap();
 
-    assert_eq!(launch.status, LaunchStatus::Completed);
-    assert!(launch.fin.is_some());
-    assert!(launch.bow.is_some());
+    assert_eq!(launch.status, LaunchStatus::LiquidityDeployed);
+    assert_eq!(
+        launch.fin.unwrap().contract_address,
+        Some(simulation.fin_address)
+    );
+    assert_eq!(
+        launch.bow.unwrap().contract_address,
+        Some(simulation.bow_address)
+    );
+
+    // nothing left to simulate once the liquidity is deployed
+    let simulation: StdResult<SimulatePostLaunchResponse> = app.wrap().query_wasm_smart(
+        keiko_addr.clone(),
+        &QueryMsg::SimulatePostLaunch { idx: launch.idx },
+    );
+    simulation.unwrap_err();
 }
 
 #[test]
@@ -743,6 +821,7 @@ fn launch_own_token() {
                     minimum_liquidity_one_side: Decimal::from_str("0.1").unwrap(),
                     default_lp_vest_cliff: 0,
                     default_lp_vest_duration: 60000,
+                    lp_vest_bounds: LpVestBounds::default(),
                 },
                 pilot: PilotConfig {
                     pilot_contract: pilot_addr.clone(),
@@ -752,6 +831,7 @@ fn launch_own_token() {
                         decimals: 6,
                     }],
                     min_raise_amount: Uint128::from(100_000_000_000u128),
+                    sale_bounds: SaleBounds::default(),
                 },
                 flows: FlowsConfig {
                     flows_contract: Addr::unchecked("flows"),
@@ -763,6 +843,8 @@ fn launch_own_token() {
                     fee_maker: Decimal256::from_str("0.00075").unwrap(),
                     fee_taker: Decimal256::from_str("0.0015").unwrap(),
                     fee_address: Addr::unchecked("fee_address"),
+                    price_precision: PricePrecisionPolicy::default(),
+                    fee_bounds: FinFeeBounds::default(),
                 },
                 bow: BowConfig {
                     code_id: bow_code_id,
@@ -780,6 +862,11 @@ fn launch_own_token() {
                     ],
                     fee: Decimal::from_str("0.001").unwrap(),
                     amp: Decimal::from_str("1").unwrap(),
+                    bounds: BowBounds::default(),
+                },
+                launch: LaunchConfig {
+                    max_active_per_owner: 1,
+                    keeper_reward: Uint128::zero(),
                 },
             },
             &[],
@@ -861,7 +948,7 @@ fn launch_own_token() {
         token_response.svg_url,
         Some("https://example.com/sned.svg".to_string())
     );
-    assert_eq!(launch.status, LaunchStatus::Created);
+    assert_eq!(launch.status, LaunchStatus::TokenSet);
 
     let tokenomics = Tokenomics {
         categories: vec![
@@ -873,6 +960,8 @@ fn launch_own_token() {
                     amount: Uint128::from(1_000_000_000_000u128),
                     flows: None,
                 }],
+                liquidity: None,
+                lp_vesting: None,
             },
             TokenomicsCategories {
                 label: "Liquidity".to_string(),
@@ -882,6 +971,8 @@ fn launch_own_token() {
                     amount: Uint128::from(100_000_000_000u128),
                     flows: None,
                 }],
+                liquidity: None,
+                lp_vesting: None,
             },
         ],
     };
@@ -909,7 +1000,7 @@ fn launch_own_token() {
 
     assert!(launch.tokenomics.is_some());
     assert_eq!(launch.tokenomics.unwrap(), tokenomics);
-    assert_eq!(launch.status, LaunchStatus::Created);
+    assert_eq!(launch.status, LaunchStatus::TokenomicsSet);
 
     let pilot_sale = CreateSale {
         title: "SNED".to_string(),
@@ -934,8 +1025,12 @@ fn launch_own_token() {
         keiko_addr.clone(),
         &&ExecuteMsg::PilotSchedule {
             idx: launch.idx,
+            round: None,
+            recipient: None,
             sale: pilot_sale.clone(),
             orca: create_orca.clone(),
+            existing_fin: None,
+            market: None,
         },
         &[],
     )
@@ -951,14 +1046,14 @@ fn launch_own_token() {
         )
         .unwrap();
 
-    assert!(launch.pilot.is_some());
-    let pilot = launch.pilot.unwrap();
+    assert!(!launch.pilots.is_empty());
+    let pilot = launch.pilots[0].clone();
     let mut pilot_sale_updated = pilot_sale.clone();
     pilot_sale_updated.beneficiary = keiko_addr.clone();
     assert_eq!(pilot.sale, pilot_sale_updated);
     assert_eq!(pilot.orca, create_orca);
     assert_eq!(pilot.beneficiary, pilot_sale.beneficiary);
-    assert_eq!(launch.status, LaunchStatus::Planned);
+    assert_eq!(launch.status, LaunchStatus::Scheduled);
 
     let mut new_block = app.block_info();
     new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
@@ -967,7 +1062,10 @@ fn launch_own_token() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotStart { idx: launch.idx },
+        &&ExecuteMsg::PilotStart {
+            idx: launch.idx,
+            round: None,
+        },
         &coins(1_000_000_000_000, "snedown"),
     )
     .unwrap();
@@ -982,8 +1080,8 @@ fn launch_own_token() {
         )
         .unwrap();
 
-    assert_eq!(launch.pilot.unwrap().idx, Some(Uint128::zero()));
-    assert_eq!(launch.status, LaunchStatus::InProgress);
+    assert_eq!(launch.pilots[0].clone().idx, Some(Uint128::zero()));
+    assert_eq!(launch.status, LaunchStatus::SaleLive);
 
     let launch: Launch = app
         .wrap()
@@ -995,15 +1093,15 @@ fn launch_own_token() {
         )
         .unwrap();
 
-    assert_eq!(launch.clone().pilot.unwrap().idx, Some(Uint128::zero()));
-    assert_eq!(launch.status, LaunchStatus::InProgress);
+    assert_eq!(launch.pilots[0].clone().idx, Some(Uint128::zero()));
+    assert_eq!(launch.status, LaunchStatus::SaleLive);
 
     let pilot_sale: kujira_pilot::SaleResponse = app
         .wrap()
         .query_wasm_smart(
             pilot_addr.clone(),
             &kujira_pilot::QueryMsg::Sale {
-                idx: launch.pilot.unwrap().idx.unwrap(),
+                idx: launch.pilots[0].clone().idx.unwrap(),
             },
         )
         .unwrap();
@@ -1041,7 +1139,10 @@ fn launch_own_token() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotExecute { idx: launch.idx },
+        &&ExecuteMsg::PilotExecute {
+            idx: launch.idx,
+            round: None,
+        },
         &[],
     )
     .unwrap_err();
@@ -1053,7 +1154,10 @@ fn launch_own_token() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotExecute { idx: launch.idx },
+        &&ExecuteMsg::PilotExecute {
+            idx: launch.idx,
+            round: None,
+        },
         &[],
     )
     .unwrap();
@@ -1068,14 +1172,14 @@ fn launch_own_token() {
         )
         .unwrap();
 
-    assert_eq!(launch.status, LaunchStatus::Completed);
+    assert_eq!(launch.status, LaunchStatus::SaleExecuted);
 
     let pilot_sale: kujira_pilot::SaleResponse = app
         .wrap()
         .query_wasm_smart(
             pilot_addr.clone(),
             &kujira_pilot::QueryMsg::Sale {
-                idx: launch.pilot.unwrap().idx.unwrap(),
+                idx: launch.pilots[0].clone().idx.unwrap(),
             },
         )
         .unwrap();
@@ -1136,7 +1240,7 @@ fn launch_own_token() {
         )
         .unwrap();
 
-    assert_eq!(launch.status, LaunchStatus::Completed);
+    assert_eq!(launch.status, LaunchStatus::LiquidityDeployed);
     assert!(launch.fin.is_some());
     assert!(launch.bow.is_some());
 }
@@ -1243,6 +1347,7 @@ fn launch_own_token_18_decimals() {
                     minimum_liquidity_one_side: Decimal::from_str("0.1").unwrap(),
                     default_lp_vest_cliff: 0,
                     default_lp_vest_duration: 60000,
+                    lp_vest_bounds: LpVestBounds::default(),
                 },
                 pilot: PilotConfig {
                     pilot_contract: pilot_addr.clone(),
@@ -1252,6 +1357,7 @@ fn launch_own_token_18_decimals() {
                         decimals: 6,
                     }],
                     min_raise_amount: Uint128::from(100_000_000_000u128),
+                    sale_bounds: SaleBounds::default(),
                 },
                 flows: FlowsConfig {
                     flows_contract: Addr::unchecked("flows"),
@@ -1263,6 +1369,8 @@ fn launch_own_token_18_decimals() {
                     fee_maker: Decimal256::from_str("0.00075").unwrap(),
                     fee_taker: Decimal256::from_str("0.0015").unwrap(),
                     fee_address: Addr::unchecked("fee_address"),
+                    price_precision: PricePrecisionPolicy::default(),
+                    fee_bounds: FinFeeBounds::default(),
                 },
                 bow: BowConfig {
                     code_id: bow_code_id,
@@ -1280,6 +1388,11 @@ fn launch_own_token_18_decimals() {
                     ],
                     fee: Decimal::from_str("0.001").unwrap(),
                     amp: Decimal::from_str("1").unwrap(),
+                    bounds: BowBounds::default(),
+                },
+                launch: LaunchConfig {
+                    max_active_per_owner: 1,
+                    keeper_reward: Uint128::zero(),
                 },
             },
             &[],
@@ -1361,7 +1474,7 @@ fn launch_own_token_18_decimals() {
         token_response.svg_url,
         Some("https://example.com/sned.svg".to_string())
     );
-    assert_eq!(launch.status, LaunchStatus::Created);
+    assert_eq!(launch.status, LaunchStatus::TokenSet);
 
     let tokenomics = Tokenomics {
         categories: vec![
@@ -1373,6 +1486,8 @@ fn launch_own_token_18_decimals() {
                     amount: Uint128::from(1_000_000_000_000_000_000_000_000u128),
                     flows: None,
                 }],
+                liquidity: None,
+                lp_vesting: None,
             },
             TokenomicsCategories {
                 label: "Liquidity".to_string(),
@@ -1382,6 +1497,8 @@ fn launch_own_token_18_decimals() {
                     amount: Uint128::from(100_000_000_000_000_000_000_000u128),
                     flows: None,
                 }],
+                liquidity: None,
+                lp_vesting: None,
             },
         ],
     };
@@ -1409,7 +1526,7 @@ fn launch_own_token_18_decimals() {
 
     assert!(launch.tokenomics.is_some());
     assert_eq!(launch.tokenomics.unwrap(), tokenomics);
-    assert_eq!(launch.status, LaunchStatus::Created);
+    assert_eq!(launch.status, LaunchStatus::TokenomicsSet);
 
     let pilot_sale = CreateSale {
         title: "SNED".to_string(),
@@ -1434,8 +1551,12 @@ fn launch_own_token_18_decimals() {
         keiko_addr.clone(),
         &&ExecuteMsg::PilotSchedule {
             idx: launch.idx,
+            round: None,
+            recipient: None,
             sale: pilot_sale.clone(),
             orca: create_orca.clone(),
+            existing_fin: None,
+            market: None,
         },
         &[],
     )
@@ -1451,14 +1572,14 @@ fn launch_own_token_18_decimals() {
         )
         .unwrap();
 
-    assert!(launch.pilot.is_some());
-    let pilot = launch.pilot.unwrap();
+    assert!(!launch.pilots.is_empty());
+    let pilot = launch.pilots[0].clone();
     let mut pilot_sale_updated = pilot_sale.clone();
     pilot_sale_updated.beneficiary = keiko_addr.clone();
     assert_eq!(pilot.sale, pilot_sale_updated);
     assert_eq!(pilot.orca, create_orca);
     assert_eq!(pilot.beneficiary, pilot_sale.beneficiary);
-    assert_eq!(launch.status, LaunchStatus::Planned);
+    assert_eq!(launch.status, LaunchStatus::Scheduled);
 
     let mut new_block = app.block_info();
     new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
@@ -1467,7 +1588,10 @@ fn launch_own_token_18_decimals() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotStart { idx: launch.idx },
+        &&ExecuteMsg::PilotStart {
+            idx: launch.idx,
+            round: None,
+        },
         &coins(1_000_000_000_000_000_000_000_000, "snedown"),
     )
     .unwrap();
@@ -1482,8 +1606,8 @@ fn launch_own_token_18_decimals() {
         )
         .unwrap();
 
-    assert_eq!(launch.pilot.unwrap().idx, Some(Uint128::zero()));
-    assert_eq!(launch.status, LaunchStatus::InProgress);
+    assert_eq!(launch.pilots[0].clone().idx, Some(Uint128::zero()));
+    assert_eq!(launch.status, LaunchStatus::SaleLive);
 
     let launch: Launch = app
         .wrap()
@@ -1495,15 +1619,15 @@ fn launch_own_token_18_decimals() {
         )
         .unwrap();
 
-    assert_eq!(launch.clone().pilot.unwrap().idx, Some(Uint128::zero()));
-    assert_eq!(launch.status, LaunchStatus::InProgress);
+    assert_eq!(launch.pilots[0].clone().idx, Some(Uint128::zero()));
+    assert_eq!(launch.status, LaunchStatus::SaleLive);
 
     let pilot_sale: kujira_pilot::SaleResponse = app
         .wrap()
         .query_wasm_smart(
             pilot_addr.clone(),
             &kujira_pilot::QueryMsg::Sale {
-                idx: launch.pilot.unwrap().idx.unwrap(),
+                idx: launch.pilots[0].clone().idx.unwrap(),
             },
         )
         .unwrap();
@@ -1541,7 +1665,10 @@ fn launch_own_token_18_decimals() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotExecute { idx: launch.idx },
+        &&ExecuteMsg::PilotExecute {
+            idx: launch.idx,
+            round: None,
+        },
         &[],
     )
     .unwrap_err();
@@ -1553,7 +1680,10 @@ fn launch_own_token_18_decimals() {
     app.execute_contract(
         Addr::unchecked("launcher"),
         keiko_addr.clone(),
-        &&ExecuteMsg::PilotExecute { idx: launch.idx },
+        &&ExecuteMsg::PilotExecute {
+            idx: launch.idx,
+            round: None,
+        },
         &[],
     )
     .unwrap();
@@ -1568,14 +1698,14 @@ fn launch_own_token_18_decimals() {
         )
         .unwrap();
 
-    assert_eq!(launch.status, LaunchStatus::Completed);
+    assert_eq!(launch.status, LaunchStatus::SaleExecuted);
 
     let pilot_sale: kujira_pilot::SaleResponse = app
         .wrap()
         .query_wasm_smart(
             pilot_addr.clone(),
             &kujira_pilot::QueryMsg::Sale {
-                idx: launch.pilot.unwrap().idx.unwrap(),
+                idx: launch.pilots[0].clone().idx.unwrap(),
             },
         )
         .unwrap();
@@ -1639,7 +1769,4446 @@ fn launch_own_token_18_decimals() {
         )
         .unwrap();
 
-    assert_eq!(launch.status, LaunchStatus::Completed);
+    assert_eq!(launch.status, LaunchStatus::LiquidityDeployed);
     assert!(launch.fin.is_some());
     assert!(launch.bow.is_some());
 }
+
+fn setup() -> (CustomApp, Addr, Addr) {
+    let mut app: CustomApp = mock_app(vec![
+        (
+            Addr::unchecked("launcher"),
+            coins(1_000_000_000_000_000, "usk"),
+        ),
+        (
+            Addr::unchecked("bidder"),
+            vec![
+                coin(1_000_000_000_000_000, "bid"),
+                coin(1_000_000_000_000_000, "bid2"),
+            ],
+        ),
+    ]);
+
+    let contract = Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply));
+    let code_id = app.store_code(contract);
+
+    let pilot_code_id = app.store_code(Box::new(
+        ContractWrapper::new(
+            kujira_pilot_testing::contract::execute,
+            kujira_pilot_testing::contract::instantiate,
+            kujira_pilot_testing::contract::query,
+        )
+        .with_reply(kujira_pilot_testing::contract::reply),
+    ));
+    let orca_code_id = app.store_code(Box::new(ContractWrapper::new(
+        kujira_orca_queue::contract::execute,
+        kujira_orca_queue::contract::instantiate,
+        kujira_orca_queue::contract::query,
+    )));
+    let fin_code_id = app.store_code(Box::new(ContractWrapper::new(
+        fin_execute,
+        fin_instantiate,
+        fin_query,
+    )));
+    let bow_code_id = app.store_code(Box::new(ContractWrapper::new(
+        bow_execute,
+        bow_instantiate,
+        bow_query,
+    )));
+    let utilities_code_id = app.store_code(Box::new(ContractWrapper::new(
+        utilities_execute,
+        utilities_instantiate,
+        utilities_query,
+    )));
+
+    let utilities_addr = app
+        .instantiate_contract(
+            utilities_code_id,
+            Addr::unchecked("sender"),
+            &fuzion_utilities::InstantiateMsg {
+                admin: Some("utilities_admin".to_string()),
+            },
+            &[],
+            "UTILITITES",
+            None,
+        )
+        .unwrap();
+
+    let pilot_addr = app
+        .instantiate_contract(
+            pilot_code_id,
+            Addr::unchecked("sender"),
+            &kujira_pilot::InstantiateMsg {
+                owner: Addr::unchecked("owner"),
+                orca_admin: Some(Addr::unchecked("orca_admin")),
+                orca_owner: Some(Addr::unchecked("orca_owner")),
+                deposit: Coin {
+                    denom: "usk".to_string(),
+                    amount: Uint128::from(1_000_000_000u128),
+                },
+                orca_code_id,
+                sale_fee: Decimal::from_str("0.05").unwrap(),
+                withdrawal_fee: Decimal::from_str("0.005").unwrap(),
+                fee_address: Addr::unchecked("fee_address"),
+            },
+            &[],
+            "KEIKO",
+            None,
+        )
+        .unwrap();
+
+    let keiko_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("sender"),
+            &InstantiateMsg {
+                owner: Addr::unchecked("owner"),
+                token: TokenConfig {
+                    denom_fee: Coin {
+                        denom: "usk".to_string(),
+                        amount: Uint128::from(10_000_000u128),
+                    },
+                    default_admin: Addr::unchecked("governance"),
+                    utilities_contract: Addr::unchecked(utilities_addr),
+                },
+                tokenomics: TokenomicsConfig {
+                    minimum_liquidity_one_side: Decimal::from_str("0.1").unwrap(),
+                    default_lp_vest_cliff: 0,
+                    default_lp_vest_duration: 60000,
+                    lp_vest_bounds: LpVestBounds::default(),
+                },
+                pilot: PilotConfig {
+                    pilot_contract: pilot_addr.clone(),
+                    allowed_bid_denoms: vec![
+                        BidDenoms {
+                            denom: Denom::from("bid"),
+                            symbol: "bid".to_string(),
+                            decimals: 6,
+                        },
+                        BidDenoms {
+                            denom: Denom::from("bid2"),
+                            symbol: "bid2".to_string(),
+                            decimals: 6,
+                        },
+                    ],
+                    min_raise_amount: Uint128::from(10_000_000_000u128),
+                    sale_bounds: SaleBounds::default(),
+                },
+                flows: FlowsConfig {
+                    flows_contract: Addr::unchecked("flows"),
+                },
+                fin: FinConfig {
+                    code_id: fin_code_id,
+                    owner: Addr::unchecked("owner"),
+                    admin: Addr::unchecked("admin"),
+                    fee_maker: Decimal256::from_str("0.00075").unwrap(),
+                    fee_taker: Decimal256::from_str("0.0015").unwrap(),
+                    fee_address: Addr::unchecked("fee_address"),
+                    price_precision: PricePrecisionPolicy::default(),
+                    fee_bounds: FinFeeBounds::default(),
+                },
+                bow: BowConfig {
+                    code_id: bow_code_id,
+                    owner: Addr::unchecked("owner"),
+                    admin: Addr::unchecked("admin"),
+                    intervals: vec![
+                        Decimal::from_str("0.001").unwrap(),
+                        Decimal::from_str("0.005").unwrap(),
+                        Decimal::from_str("0.01").unwrap(),
+                    ],
+                    fee: Decimal::from_str("0.001").unwrap(),
+                    amp: Decimal::from_str("1").unwrap(),
+                    bounds: BowBounds::default(),
+                },
+                launch: LaunchConfig {
+                    max_active_per_owner: 1,
+                    keeper_reward: Uint128::from(1_000_000u128),
+                },
+            },
+            &[],
+            "KEIKO",
+            None,
+        )
+        .unwrap();
+
+    (app, keiko_addr, pilot_addr)
+}
+
+/// categories with the sale and liquidity allocations used by `execute_sale`
+fn sale_categories(liquidity: Option<Vec<LiquidityTarget>>) -> Vec<TokenomicsCategories> {
+    vec![
+        TokenomicsCategories {
+            label: "Sale".to_string(),
+            category_type: CategoryTypes::Sale,
+            recipients: vec![TokenomicsRecipient {
+                address: None,
+                amount: Uint128::from(1_000_000_000_000u128),
+                flows: None,
+            }],
+            liquidity: None,
+            lp_vesting: None,
+        },
+        TokenomicsCategories {
+            label: "Liquidity".to_string(),
+            category_type: CategoryTypes::Liquidity,
+            recipients: vec![TokenomicsRecipient {
+                address: None,
+                amount: Uint128::from(100_000_000_000u128),
+                flows: None,
+            }],
+            liquidity,
+            lp_vesting: None,
+        },
+    ]
+}
+
+/// creates a launch with a managed token and schedules its Pilot sale to open in 100 seconds
+fn schedule_sale(
+    app: &mut CustomApp,
+    keiko_addr: &Addr,
+    categories: Vec<TokenomicsCategories>,
+) -> Uint128 {
+    schedule_sale_with_market(app, keiko_addr, categories, None)
+}
+
+/// creates a launch with a managed token and schedules its Pilot sale with the market parameters
+fn schedule_sale_with_market(
+    app: &mut CustomApp,
+    keiko_addr: &Addr,
+    categories: Vec<TokenomicsCategories>,
+    market: Option<MarketParams>,
+) -> Uint128 {
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    let launches: Vec<Launch> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launches {
+                start_after: None,
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    let idx = launches[0].idx;
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx,
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics { idx, categories },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotSchedule {
+            idx,
+            round: None,
+            recipient: None,
+            sale: CreateSale {
+                title: "SNED".to_string(),
+                description: "SNED Launch".to_string(),
+                url: "https://example.com/sned".to_string(),
+                beneficiary: Addr::unchecked("beneficiary"),
+                price: Decimal::from_str("1").unwrap(),
+                opens: Timestamp::from_seconds(app.block_info().time.seconds() + 100),
+                closes: Timestamp::from_seconds(app.block_info().time.seconds() + 1000),
+            },
+            orca: CreateOrca {
+                bid_denom: Denom::from("bid".to_string()),
+                max_slot: 9,
+                premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+                bid_threshold: Uint128::from(1_000_000_000u128),
+                waiting_period: 600,
+            },
+            existing_fin: None,
+            market,
+        },
+        &[],
+    )
+    .unwrap();
+
+    idx
+}
+
+/// creates a launch with a managed token and runs its Pilot sale until it is executed
+fn execute_sale(
+    app: &mut CustomApp,
+    keiko_addr: &Addr,
+    pilot_addr: &Addr,
+    categories: Vec<TokenomicsCategories>,
+) -> Uint128 {
+    execute_sale_with_market(app, keiko_addr, pilot_addr, categories, None)
+}
+
+/// runs the Pilot sale of a launch scheduled with the market parameters until it is executed
+fn execute_sale_with_market(
+    app: &mut CustomApp,
+    keiko_addr: &Addr,
+    pilot_addr: &Addr,
+    categories: Vec<TokenomicsCategories>,
+    market: Option<MarketParams>,
+) -> Uint128 {
+    let idx = schedule_sale_with_market(app, keiko_addr, categories, market);
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotStart { idx, round: None },
+        &[],
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Launch { idx })
+        .unwrap();
+    let pilot_sale: kujira_pilot::SaleResponse = app
+        .wrap()
+        .query_wasm_smart(
+            pilot_addr.clone(),
+            &kujira_pilot::QueryMsg::Sale {
+                idx: launch.pilots[0].clone().idx.unwrap(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("bidder"),
+        pilot_sale.orca_address,
+        &kujira_orca::ExecuteMsg::SubmitBid {
+            premium_slot: 9,
+            delegate: None,
+            proof: None,
+        },
+        &[coin(600_000_000_000, "bid")],
+    )
+    .unwrap();
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 901);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotExecute { idx, round: None },
+        &[],
+    )
+    .unwrap();
+
+    idx
+}
+
+#[test]
+fn cancel_launch() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_020_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: Some("https://example.com/sned.png".to_string()),
+            svg_url: Some("https://example.com/sned.svg".to_string()),
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("someone"),
+            keiko_addr.clone(),
+            &ExecuteMsg::CancelLaunch {
+                idx: Uint128::zero(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let balance_before = app
+        .wrap()
+        .query_balance(Addr::unchecked("launcher"), "usk")
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::CancelLaunch {
+            idx: Uint128::zero(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let balance_after = app
+        .wrap()
+        .query_balance(Addr::unchecked("launcher"), "usk")
+        .unwrap();
+
+    // the deposit and the prefunded funds are refunded
+    assert_eq!(
+        balance_after.amount - balance_before.amount,
+        Uint128::from(1_020_000_000u128)
+    );
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::Cancelled);
+    assert!(launch.prefunded.is_empty());
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::CancelLaunch {
+                idx: Uint128::zero(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::InvalidTransition("Cancelled".to_string(), "Cancel".to_string())
+    );
+
+    // a cancelled launch does not block the owner from creating a new one
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr,
+            &QueryMsg::Launch {
+                idx: Uint128::one(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::Created);
+}
+
+#[test]
+fn max_active_launches_per_owner() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Create {
+                terms_conditions_accepted: true,
+            },
+            &coins(1_000_000_000, "usk"),
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::MaxActiveLaunches("launcher".to_string(), "1".to_string())
+    );
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        keiko_addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            token: None,
+            tokenomics: None,
+            pilot: None,
+            flows: None,
+            fin: Box::new(None),
+            bow: None,
+            launch: Some(LaunchConfig {
+                max_active_per_owner: 2,
+                keeper_reward: Uint128::zero(),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap_err();
+
+    // terminal launches are not counted
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::CancelLaunch {
+            idx: Uint128::zero(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    let launches: Vec<Launch> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr,
+            &QueryMsg::LaunchesByOwner {
+                owner: Addr::unchecked("launcher"),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launches.len(), 3);
+}
+
+#[test]
+fn launch_state_machine() {
+    use crate::launch::next_status;
+
+    assert_eq!(
+        next_status(&LaunchStatus::Created, &LaunchAction::Token),
+        Some(LaunchStatus::TokenSet)
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::Created, &LaunchAction::Tokenomics),
+        None
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::Scheduled, &LaunchAction::PilotSchedule),
+        Some(LaunchStatus::Scheduled)
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::SaleLive, &LaunchAction::PostLaunch),
+        Some(LaunchStatus::LiquidityDeployed)
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::SaleExecuted, &LaunchAction::PilotRetract),
+        None
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::SaleLive, &LaunchAction::Cancel),
+        None
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::LiquidityDeployed, &LaunchAction::LaunchFin),
+        Some(LaunchStatus::Finalized)
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::SaleExecuted, &LaunchAction::PilotStart),
+        Some(LaunchStatus::SaleLive)
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::SaleLive, &LaunchAction::RoundRetract),
+        Some(LaunchStatus::SaleExecuted)
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::Scheduled, &LaunchAction::Allowlist),
+        Some(LaunchStatus::Scheduled)
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::SaleLive, &LaunchAction::Allowlist),
+        None
+    );
+    assert_eq!(
+        next_status(&LaunchStatus::SaleLive, &LaunchAction::PricePrecision),
+        Some(LaunchStatus::SaleLive)
+    );
+    assert_eq!(
+        next_status(
+            &LaunchStatus::LiquidityDeployed,
+            &LaunchAction::PricePrecision
+        ),
+        None
+    );
+
+    for status in [
+        LaunchStatus::Retracted,
+        LaunchStatus::Finalized,
+        LaunchStatus::Cancelled,
+    ] {
+        assert!(status.is_terminal());
+        assert_eq!(next_status(&status, &LaunchAction::PostLaunch), None);
+        assert_eq!(next_status(&status, &LaunchAction::Cancel), None);
+    }
+}
+
+#[test]
+fn launch_history() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: Some("https://example.com/sned.png".to_string()),
+            svg_url: Some("https://example.com/sned.svg".to_string()),
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::CancelLaunch {
+            idx: Uint128::zero(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let history: Vec<LaunchHistoryEntry> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::LaunchHistory {
+                idx: Uint128::zero(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(history.len(), 3);
+    assert_eq!(
+        history
+            .iter()
+            .map(|h| h.action.clone())
+            .collect::<Vec<LaunchAction>>(),
+        vec![
+            LaunchAction::Create,
+            LaunchAction::Token,
+            LaunchAction::Cancel
+        ]
+    );
+    assert_eq!(history[1].from, LaunchStatus::Created);
+    assert_eq!(history[1].to, LaunchStatus::TokenSet);
+    assert_eq!(history[2].to, LaunchStatus::Cancelled);
+    assert_eq!(history[2].sender, Addr::unchecked("launcher"));
+    assert_eq!(history[2].height, app.block_info().height);
+
+    let history: Vec<LaunchHistoryEntry> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr,
+            &QueryMsg::LaunchHistory {
+                idx: Uint128::zero(),
+                start_after: Some(0),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].action, LaunchAction::Token);
+}
+
+#[test]
+fn reply_ids() {
+    use crate::msg::{ReplyInfo, ReplyTypes};
+
+    let create = ReplyInfo {
+        reply_type: ReplyTypes::Create,
+        idx: Uint128::from(1u128),
+        sale: 0,
+    };
+    let execute = ReplyInfo {
+        reply_type: ReplyTypes::Execute,
+        idx: Uint128::from(1u128),
+        sale: 0,
+    };
+    let create_other = ReplyInfo {
+        reply_type: ReplyTypes::Create,
+        idx: Uint128::from(2u128),
+        sale: 0,
+    };
+
+    let create_sale = ReplyInfo {
+        reply_type: ReplyTypes::Create,
+        idx: Uint128::from(1u128),
+        sale: 1,
+    };
+
+    assert_eq!(create.id().unwrap(), 257);
+    assert_ne!(create.id().unwrap(), execute.id().unwrap());
+    assert_ne!(create.id().unwrap(), create_other.id().unwrap());
+    assert_ne!(create.id().unwrap(), create_sale.id().unwrap());
+
+    // launch idxs above 48 bits would overlap the sale byte
+    let overflow = ReplyInfo {
+        reply_type: ReplyTypes::Create,
+        idx: Uint128::from(1u128 << 48),
+        sale: 0,
+    };
+    assert_eq!(
+        overflow.id().unwrap_err(),
+        ContractError::ReplyIdOverflow((1u128 << 48).to_string())
+    );
+}
+
+#[test]
+fn pilot_sale_idx_from_create_response() {
+    use crate::contract::parse_pilot_sale_idx;
+    use cosmwasm_std::{Event, SubMsgResponse};
+
+    let pilot = Addr::unchecked("pilot");
+
+    // the data of an execute submessage is a MsgExecuteContractResponse, whose data field 1
+    // holds the data set by the contract
+    let execute_response = |data: &[u8]| -> Binary {
+        let mut encoded = vec![0x0a, data.len() as u8];
+        encoded.extend_from_slice(data);
+        Binary::from(encoded)
+    };
+
+    // the response data is preferred over the events
+    let response = SubMsgResponse {
+        events: vec![Event::new("wasm")
+            .add_attribute("_contract_address", "pilot")
+            .add_attribute("sale", "7")],
+        data: Some(execute_response(
+            to_json_binary(&Uint128::from(3u128)).unwrap().as_slice(),
+        )),
+    };
+    assert_eq!(
+        parse_pilot_sale_idx(&response, &pilot).unwrap(),
+        Uint128::from(3u128)
+    );
+
+    // falls back to the sale attribute of the pilot contract and ignores other contracts
+    let response = SubMsgResponse {
+        events: vec![
+            Event::new("wasm")
+                .add_attribute("_contract_address", "orca")
+                .add_attribute("sale", "1"),
+            Event::new("wasm")
+                .add_attribute("_contract_address", "pilot")
+                .add_attribute("sale", "7"),
+        ],
+        data: Some(execute_response(b"not an idx")),
+    };
+    assert_eq!(
+        parse_pilot_sale_idx(&response, &pilot).unwrap(),
+        Uint128::from(7u128)
+    );
+
+    // the contract data is never read from the unwrapped response data
+    let response = SubMsgResponse {
+        events: vec![Event::new("wasm")
+            .add_attribute("_contract_address", "pilot")
+            .add_attribute("sale", "7")],
+        data: Some(to_json_binary(&Uint128::from(3u128)).unwrap()),
+    };
+    assert_eq!(
+        parse_pilot_sale_idx(&response, &pilot).unwrap(),
+        Uint128::from(7u128)
+    );
+
+    // no idx in the data or the pilot events
+    let response = SubMsgResponse {
+        events: vec![Event::new("wasm")
+            .add_attribute("_contract_address", "orca")
+            .add_attribute("sale", "1")],
+        data: None,
+    };
+    assert_eq!(
+        parse_pilot_sale_idx(&response, &pilot).unwrap_err(),
+        ContractError::PilotSaleIdxNotFound {}
+    );
+}
+
+#[test]
+fn post_launch_liquidity_targets() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Tokenomics {
+                idx: Uint128::zero(),
+                categories: sale_categories(Some(vec![
+                    LiquidityTarget {
+                        destination: LiquidityDestination::BowMarketMaker,
+                        share: Decimal::from_str("0.3").unwrap(),
+                    },
+                    LiquidityTarget {
+                        destination: LiquidityDestination::BowMarketMaker,
+                        share: Decimal::from_str("0.3").unwrap(),
+                    },
+                ])),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::InvalidLiquidityShares("0.6".to_string())
+    );
+
+    let mut categories = sale_categories(None);
+    categories[0].liquidity = Some(vec![LiquidityTarget {
+        destination: LiquidityDestination::BowMarketMaker,
+        share: Decimal::one(),
+    }]);
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Tokenomics {
+                idx: Uint128::zero(),
+                categories,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::LiquidityTargetsNotAllowed("Sale".to_string())
+    );
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::CancelLaunch {
+            idx: Uint128::zero(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let idx = execute_sale(
+        &mut app,
+        &keiko_addr,
+        &pilot_addr,
+        sale_categories(Some(vec![
+            LiquidityTarget {
+                destination: LiquidityDestination::BowMarketMaker,
+                share: Decimal::from_str("0.5").unwrap(),
+            },
+            LiquidityTarget {
+                destination: LiquidityDestination::BowStable {
+                    intervals: vec![Decimal::from_str("0.0001").unwrap()],
+                    fee: Decimal::from_str("0.0005").unwrap(),
+                    amp: Decimal::from_str("10").unwrap(),
+                },
+                share: Decimal::from_str("0.5").unwrap(),
+            },
+        ])),
+    );
+
+    // one denom fee for each BOW created
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PostLaunch { idx },
+            &coins(10_000_000, "usk"),
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::InvalidDeposit("20000000".to_string(), "10000000".to_string())
+    );
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PostLaunch { idx },
+        &coins(20_000_000, "usk"),
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Launch { idx })
+        .unwrap();
+    let liquidity = launch.liquidity.unwrap();
+
+    assert_eq!(liquidity.len(), 2);
+    assert_eq!(
+        launch.bow.unwrap().contract_address,
+        Some(liquidity[0].bow.clone())
+    );
+    assert_ne!(liquidity[0].bow, liquidity[1].bow);
+    for position in liquidity {
+        assert!(position.lp.is_none());
+        assert_eq!(position.deposit.len(), 2);
+        assert_eq!(
+            position
+                .deposit
+                .iter()
+                .find(|c| c.denom == "bid")
+                .unwrap()
+                .amount,
+            Uint128::from(5_000_000_000u128)
+        );
+    }
+}
+
+#[test]
+fn pilot_schedule_existing_fin() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories: sale_categories(None),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the Pilot contract is not a FIN pair of the token and the bid denom
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotSchedule {
+                idx: Uint128::zero(),
+                round: None,
+                recipient: None,
+                sale: CreateSale {
+                    title: "SNED".to_string(),
+                    description: "SNED Launch".to_string(),
+                    url: "https://example.com/sned".to_string(),
+                    beneficiary: Addr::unchecked("beneficiary"),
+                    price: Decimal::from_str("1").unwrap(),
+                    opens: Timestamp::from_seconds(app.block_info().time.seconds() + 100),
+                    closes: Timestamp::from_seconds(app.block_info().time.seconds() + 1000),
+                },
+                orca: CreateOrca {
+                    bid_denom: Denom::from("bid".to_string()),
+                    max_slot: 9,
+                    premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+                    bid_threshold: Uint128::from(1_000_000_000u128),
+                    waiting_period: 600,
+                },
+                existing_fin: Some(pilot_addr.clone()),
+                market: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::InvalidExistingFin(pilot_addr.to_string())
+    );
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::TokenomicsSet);
+    assert!(launch.existing_fin.is_none());
+}
+
+#[test]
+fn predicted_addresses_existing_bow() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    // the first liquidity target deposits to an existing BOW pool
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories: sale_categories(Some(vec![
+                LiquidityTarget {
+                    destination: LiquidityDestination::ExistingFin {
+                        fin: Addr::unchecked("existing_fin"),
+                        bow: Addr::unchecked("existing_bow"),
+                    },
+                    share: Decimal::percent(50),
+                },
+                LiquidityTarget {
+                    destination: LiquidityDestination::BowMarketMaker,
+                    share: Decimal::percent(50),
+                },
+            ])),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let predicted: PredictedAddressesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::PredictedAddresses {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(predicted.bow, Addr::unchecked("existing_bow"));
+}
+
+#[test]
+fn lp_vesting_plan() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    let mut categories = sale_categories(None);
+    categories[1].lp_vesting = Some(vec![LpVestingTranche {
+        taker: None,
+        share: Decimal::one(),
+        cliff: 1000,
+        duration: 100,
+    }]);
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Tokenomics {
+                idx: Uint128::zero(),
+                categories,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::LpVestingOutOfBounds("1000".to_string(), "100".to_string())
+    );
+
+    let mut categories = sale_categories(None);
+    categories[1].lp_vesting = Some(vec![LpVestingTranche {
+        taker: None,
+        share: Decimal::from_str("0.5").unwrap(),
+        cliff: 0,
+        duration: 100,
+    }]);
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Tokenomics {
+                idx: Uint128::zero(),
+                categories,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::InvalidLpVestingShares("0.5".to_string())
+    );
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::CancelLaunch {
+            idx: Uint128::zero(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let mut categories = sale_categories(None);
+    categories[1].lp_vesting = Some(vec![
+        LpVestingTranche {
+            taker: None,
+            share: Decimal::from_str("0.75").unwrap(),
+            cliff: 100,
+            duration: 1000,
+        },
+        LpVestingTranche {
+            taker: Some(Addr::unchecked("treasury")),
+            share: Decimal::from_str("0.25").unwrap(),
+            cliff: 0,
+            duration: 500,
+        },
+    ]);
+    let idx = execute_sale(&mut app, &keiko_addr, &pilot_addr, categories);
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Launch { idx })
+        .unwrap();
+
+    let flows = crate::post_launch::lp_flows(
+        &config,
+        &launch,
+        &coin(1_001, "ulp"),
+        &Denom::from("bid"),
+        10,
+    );
+
+    assert_eq!(flows.len(), 2);
+    assert_eq!(flows[0].taker, Addr::unchecked("beneficiary"));
+    assert_eq!(flows[0].schedules[0].amount, Uint128::from(750u128));
+    assert_eq!(flows[0].schedules[0].cliff_end_time, 110);
+    assert_eq!(flows[0].schedules[0].end_time, 1010);
+    assert_eq!(flows[1].taker, Addr::unchecked("treasury"));
+    assert_eq!(flows[1].schedules[0].amount, Uint128::from(251u128));
+    assert_eq!(flows[1].denom, "ulp".to_string());
+}
+
+#[test]
+fn multiple_sale_rounds() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    let mut categories = sale_categories(None);
+    categories[0].label = "Seed".to_string();
+    categories[0].recipients[0].amount = Uint128::from(500_000_000_000u128);
+    let mut public = categories[0].clone();
+    public.label = "Public".to_string();
+    categories.push(public);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let schedule = |app: &CustomApp, round: u8| ExecuteMsg::PilotSchedule {
+        idx: Uint128::zero(),
+        round: Some(round),
+        recipient: None,
+        sale: CreateSale {
+            title: "SNED".to_string(),
+            description: "SNED Launch".to_string(),
+            url: "https://example.com/sned".to_string(),
+            beneficiary: Addr::unchecked(format!("beneficiary{round}")),
+            price: Decimal::from_str("1").unwrap(),
+            opens: Timestamp::from_seconds(app.block_info().time.seconds() + 100),
+            closes: Timestamp::from_seconds(app.block_info().time.seconds() + 1000),
+        },
+        orca: CreateOrca {
+            bid_denom: Denom::from("bid".to_string()),
+            max_slot: 9,
+            premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+            bid_threshold: Uint128::from(1_000_000_000u128),
+            waiting_period: 600,
+        },
+        existing_fin: None,
+        market: None,
+    };
+
+    // rounds are scheduled in order and only for the sale categories
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &schedule(&app, 1),
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::InvalidRound("1".to_string()));
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &schedule(&app, 0),
+        &[],
+    )
+    .unwrap();
+
+    for round in 0..2u8 {
+        if round == 1 {
+            // the next round can be scheduled once the previous round has been executed
+            app.execute_contract(
+                Addr::unchecked("launcher"),
+                keiko_addr.clone(),
+                &schedule(&app, 1),
+                &[],
+            )
+            .unwrap();
+
+            let err = app
+                .execute_contract(
+                    Addr::unchecked("launcher"),
+                    keiko_addr.clone(),
+                    &schedule(&app, 2),
+                    &[],
+                )
+                .unwrap_err()
+                .downcast::<ContractError>()
+                .unwrap();
+
+            assert_eq!(err, ContractError::InvalidRound("2".to_string()));
+        }
+
+        let mut new_block = app.block_info();
+        new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+        app.set_block(new_block);
+
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotStart {
+                idx: Uint128::zero(),
+                round: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let launch: Launch = app
+            .wrap()
+            .query_wasm_smart(
+                keiko_addr.clone(),
+                &QueryMsg::Launch {
+                    idx: Uint128::zero(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(launch.status, LaunchStatus::SaleLive);
+        assert_eq!(launch.started_round(), Some(round));
+
+        let sale_idx = launch.pilots[round as usize].idx.unwrap();
+        let by_pilot_idx: Launch = app
+            .wrap()
+            .query_wasm_smart(
+                keiko_addr.clone(),
+                &QueryMsg::LaunchByPilotIdx { idx: sale_idx },
+            )
+            .unwrap();
+
+        assert_eq!(by_pilot_idx.idx, launch.idx);
+
+        let pilot_sale: kujira_pilot::SaleResponse = app
+            .wrap()
+            .query_wasm_smart(
+                pilot_addr.clone(),
+                &kujira_pilot::QueryMsg::Sale { idx: sale_idx },
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bidder"),
+            pilot_sale.orca_address,
+            &kujira_orca::ExecuteMsg::SubmitBid {
+                premium_slot: 9,
+                delegate: None,
+                proof: None,
+            },
+            &[coin(600_000_000_000, "bid")],
+        )
+        .unwrap();
+
+        let mut new_block = app.block_info();
+        new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 901);
+        app.set_block(new_block);
+
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotExecute {
+                idx: Uint128::zero(),
+                round: Some(round),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::SaleExecuted);
+    assert_eq!(launch.pilots.len(), 2);
+    assert!(launch.pilots.iter().all(|p| p.result.is_some()));
+
+    // liquidity is priced from the raise of both rounds
+    let simulation: SimulatePostLaunchResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::SimulatePostLaunch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    let raise_total = launch
+        .pilots
+        .iter()
+        .map(|p| p.result.clone().unwrap().raise_total)
+        .fold(Uint128::zero(), |acc, x| acc + x);
+    assert_eq!(
+        simulation.lp_stable_amount,
+        raise_total.multiply_ratio(1u128, 10u128)
+    );
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PostLaunch {
+            idx: Uint128::zero(),
+        },
+        &simulation.required_deposit,
+    )
+    .unwrap();
+
+    for round in 0..2 {
+        let balance = app
+            .wrap()
+            .query_balance(format!("beneficiary{round}"), "bid")
+            .unwrap();
+        assert!(!balance.amount.is_zero());
+    }
+}
+
+#[test]
+fn retracted_sale_round() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    let mut categories = sale_categories(None);
+    categories[0].label = "Seed".to_string();
+    categories[0].recipients[0].amount = Uint128::from(500_000_000_000u128);
+    let mut public = categories[0].clone();
+    public.label = "Public".to_string();
+    public.recipients[0].amount = Uint128::from(250_000_000_000u128);
+    let mut community = public.clone();
+    community.label = "Community".to_string();
+    categories.push(public);
+    categories.push(community);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let schedule = |app: &CustomApp, round: u8| ExecuteMsg::PilotSchedule {
+        idx: Uint128::zero(),
+        round: Some(round),
+        recipient: None,
+        sale: CreateSale {
+            title: "SNED".to_string(),
+            description: "SNED Launch".to_string(),
+            url: "https://example.com/sned".to_string(),
+            beneficiary: Addr::unchecked(format!("beneficiary{round}")),
+            price: Decimal::from_str("1").unwrap(),
+            opens: Timestamp::from_seconds(app.block_info().time.seconds() + 100),
+            closes: Timestamp::from_seconds(app.block_info().time.seconds() + 1000),
+        },
+        orca: CreateOrca {
+            bid_denom: Denom::from("bid".to_string()),
+            max_slot: 9,
+            premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+            bid_threshold: Uint128::from(1_000_000_000u128),
+            waiting_period: 600,
+        },
+        existing_fin: None,
+        market: None,
+    };
+
+    // the second round is retracted, the first and the third round are executed
+    for round in 0..3u8 {
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &schedule(&app, round),
+            &[],
+        )
+        .unwrap();
+
+        let mut new_block = app.block_info();
+        new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+        app.set_block(new_block);
+
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotStart {
+                idx: Uint128::zero(),
+                round: Some(round),
+            },
+            &[],
+        )
+        .unwrap();
+
+        if round == 1 {
+            app.execute_contract(
+                Addr::unchecked("launcher"),
+                keiko_addr.clone(),
+                &ExecuteMsg::PilotRetract {
+                    idx: Uint128::zero(),
+                    round: Some(round),
+                },
+                &[],
+            )
+            .unwrap();
+
+            let launch: Launch = app
+                .wrap()
+                .query_wasm_smart(
+                    keiko_addr.clone(),
+                    &QueryMsg::Launch {
+                        idx: Uint128::zero(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(launch.status, LaunchStatus::SaleExecuted);
+
+            // the minted tokens of the retracted round are burned as the launch continues
+            let balance = app
+                .wrap()
+                .query_balance("launcher", launch.token.unwrap().denom.to_string())
+                .unwrap();
+            assert!(balance.amount.is_zero());
+            continue;
+        }
+
+        let launch: Launch = app
+            .wrap()
+            .query_wasm_smart(
+                keiko_addr.clone(),
+                &QueryMsg::Launch {
+                    idx: Uint128::zero(),
+                },
+            )
+            .unwrap();
+
+        let pilot_sale: kujira_pilot::SaleResponse = app
+            .wrap()
+            .query_wasm_smart(
+                pilot_addr.clone(),
+                &kujira_pilot::QueryMsg::Sale {
+                    idx: launch.pilots[round as usize].idx.unwrap(),
+                },
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bidder"),
+            pilot_sale.orca_address,
+            &kujira_orca::ExecuteMsg::SubmitBid {
+                premium_slot: 9,
+                delegate: None,
+                proof: None,
+            },
+            &[coin(600_000_000_000, "bid")],
+        )
+        .unwrap();
+
+        let mut new_block = app.block_info();
+        new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 901);
+        app.set_block(new_block);
+
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotExecute {
+                idx: Uint128::zero(),
+                round: Some(round),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::SaleExecuted);
+    assert!(launch.pilots[1].result.is_none());
+
+    // only the liquidity share of the executed rounds is deployed, paired at their raise
+    let simulation: SimulatePostLaunchResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::SimulatePostLaunch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    let raise_total = [0, 2]
+        .iter()
+        .map(|sale| launch.pilots[*sale].result.clone().unwrap().raise_total)
+        .fold(Uint128::zero(), |acc, x| acc + x);
+    assert_eq!(
+        simulation.markets[0].lp_token_amount,
+        Uint128::from(75_000_000_000u128)
+    );
+    assert_eq!(
+        simulation.lp_stable_amount,
+        raise_total.multiply_ratio(1u128, 10u128)
+    );
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PostLaunch {
+            idx: Uint128::zero(),
+        },
+        &simulation.required_deposit,
+    )
+    .unwrap();
+
+    for round in [0, 2] {
+        let balance = app
+            .wrap()
+            .query_balance(format!("beneficiary{round}"), "bid")
+            .unwrap();
+        assert!(!balance.amount.is_zero());
+    }
+    let balance = app.wrap().query_balance("beneficiary1", "bid").unwrap();
+    assert!(balance.amount.is_zero());
+}
+
+#[test]
+fn multiple_bid_denoms() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    // the sale allocation is split between a sale in each bid denom
+    let mut categories = sale_categories(None);
+    categories[0].recipients[0].amount = Uint128::from(500_000_000_000u128);
+    categories[0]
+        .recipients
+        .push(categories[0].recipients[0].clone());
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let schedule = |app: &CustomApp, recipient: u8, bid_denom: &str| ExecuteMsg::PilotSchedule {
+        idx: Uint128::zero(),
+        round: None,
+        recipient: Some(recipient),
+        sale: CreateSale {
+            title: "SNED".to_string(),
+            description: "SNED Launch".to_string(),
+            url: "https://example.com/sned".to_string(),
+            beneficiary: Addr::unchecked(format!("beneficiary_{bid_denom}")),
+            price: Decimal::from_str("1").unwrap(),
+            opens: Timestamp::from_seconds(app.block_info().time.seconds() + 100),
+            closes: Timestamp::from_seconds(app.block_info().time.seconds() + 1000),
+        },
+        orca: CreateOrca {
+            bid_denom: Denom::from(bid_denom.to_string()),
+            max_slot: 9,
+            premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+            bid_threshold: Uint128::from(1_000_000_000u128),
+            waiting_period: 600,
+        },
+        existing_fin: None,
+        market: None,
+    };
+
+    for (recipient, bid_denom) in [(0, "bid"), (1, "bid2")] {
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &schedule(&app, recipient, bid_denom),
+            &[],
+        )
+        .unwrap();
+    }
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &schedule(&app, 2, "bid"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::InvalidRound("0".to_string()));
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    // the round needs the owner's Pilot deposit for its second sale, a keeper cannot start it
+    let err = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            keiko_addr.clone(),
+            &ExecuteMsg::KeeperStart {
+                idx: Uint128::zero(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::MultiSaleKeeperRound("0".to_string()));
+
+    // the second sale of the round requires its own Pilot deposit
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotStart {
+                idx: Uint128::zero(),
+                round: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::InvalidFunds {});
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotStart {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::SaleLive);
+    assert!(launch.pilots.iter().all(|p| p.idx.is_some()));
+
+    for pilot in launch.pilots.iter() {
+        let pilot_sale: kujira_pilot::SaleResponse = app
+            .wrap()
+            .query_wasm_smart(
+                pilot_addr.clone(),
+                &kujira_pilot::QueryMsg::Sale {
+                    idx: pilot.idx.unwrap(),
+                },
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bidder"),
+            pilot_sale.orca_address,
+            &kujira_orca::ExecuteMsg::SubmitBid {
+                premium_slot: 9,
+                delegate: None,
+                proof: None,
+            },
+            &[coin(600_000_000_000, pilot.orca.bid_denom.to_string())],
+        )
+        .unwrap();
+    }
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 901);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotExecute {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // a FIN pair and BOW pool per bid denom, each with half of the liquidity allocation as both
+    // sales sold the same
+    let simulation: SimulatePostLaunchResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::SimulatePostLaunch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(simulation.markets.len(), 2);
+    assert_eq!(simulation.markets[0].bid_denom, Denom::from("bid"));
+    assert_eq!(simulation.markets[1].bid_denom, Denom::from("bid2"));
+    assert_ne!(
+        simulation.markets[0].fin_address,
+        simulation.markets[1].fin_address
+    );
+    assert_eq!(simulation.liquidity.len(), 2);
+    assert_eq!(simulation.required_deposit, vec![coin(20_000_000, "usk")]);
+    let token_denom = launch.token.clone().unwrap().denom.to_string();
+    for (position, market) in simulation.liquidity.iter().zip(simulation.markets.iter()) {
+        assert!(position
+            .deposit
+            .contains(&coin(50_000_000_000, token_denom.clone())));
+        assert!(position.deposit.contains(&coin(
+            market.lp_stable_amount.u128(),
+            market.bid_denom.to_string()
+        )));
+    }
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PostLaunch {
+            idx: Uint128::zero(),
+        },
+        &simulation.required_deposit,
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    let markets = launch.markets.clone().unwrap();
+    assert_eq!(markets.len(), 2);
+    assert!(markets.iter().all(|m| !m.existing));
+
+    // the LP tokens of each market vest to the beneficiary of its sale
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    for market in markets.iter() {
+        let flows = crate::post_launch::lp_flows(
+            &config,
+            &launch,
+            &coin(1_000, "ulp"),
+            &market.bid_denom,
+            10,
+        );
+        assert_eq!(flows.len(), 1);
+        assert_eq!(
+            flows[0].taker,
+            Addr::unchecked(format!("beneficiary_{}", market.bid_denom))
+        );
+    }
+
+    for market in simulation.markets {
+        let balance = app
+            .wrap()
+            .query_balance(
+                format!("beneficiary_{}", market.bid_denom),
+                market.bid_denom.to_string(),
+            )
+            .unwrap();
+        assert_eq!(balance, market.beneficiary_funds);
+    }
+}
+
+#[test]
+fn too_many_sales() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    // the sale indexes of the reply ids would wrap past 256 sales
+    let mut categories = sale_categories(None);
+    categories[0].recipients[0].amount = Uint128::from(1_000_000_000u128);
+    let recipient = categories[0].recipients[0].clone();
+    categories[0].recipients = vec![recipient; 257];
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Tokenomics {
+                idx: Uint128::zero(),
+                categories,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::TooManySales("257".to_string()));
+}
+
+#[test]
+fn multiple_bid_denoms_different_prices() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    let mut categories = sale_categories(None);
+    categories[0].recipients[0].amount = Uint128::from(500_000_000_000u128);
+    categories[0]
+        .recipients
+        .push(categories[0].recipients[0].clone());
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories,
+        },
+        &[],
+    )
+    .unwrap();
+
+    for (recipient, bid_denom) in [(0u8, "bid"), (1, "bid2")] {
+        let now = app.block_info().time.seconds();
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotSchedule {
+                idx: Uint128::zero(),
+                round: None,
+                recipient: Some(recipient),
+                sale: CreateSale {
+                    title: "SNED".to_string(),
+                    description: "SNED Launch".to_string(),
+                    url: "https://example.com/sned".to_string(),
+                    beneficiary: Addr::unchecked(format!("beneficiary_{bid_denom}")),
+                    price: Decimal::from_str("1").unwrap(),
+                    opens: Timestamp::from_seconds(now + 100),
+                    closes: Timestamp::from_seconds(now + 1000),
+                },
+                orca: CreateOrca {
+                    bid_denom: Denom::from(bid_denom.to_string()),
+                    max_slot: 9,
+                    premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+                    bid_threshold: Uint128::from(1_000_000_000u128),
+                    waiting_period: 600,
+                },
+                existing_fin: None,
+                market: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotStart {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    // the bid sale clears without a premium, the bid2 sale at the highest premium
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    for (pilot, premium_slot) in launch.pilots.iter().zip([0u8, 9]) {
+        let pilot_sale: kujira_pilot::SaleResponse = app
+            .wrap()
+            .query_wasm_smart(
+                pilot_addr.clone(),
+                &kujira_pilot::QueryMsg::Sale {
+                    idx: pilot.idx.unwrap(),
+                },
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("bidder"),
+            pilot_sale.orca_address,
+            &kujira_orca::ExecuteMsg::SubmitBid {
+                premium_slot,
+                delegate: None,
+                proof: None,
+            },
+            &[coin(600_000_000_000, pilot.orca.bid_denom.to_string())],
+        )
+        .unwrap();
+    }
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 901);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotExecute {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // both sales sold the same, so each market gets half of the liquidity allocation paired at
+    // its own price, well within what each of them raised
+    let simulation: SimulatePostLaunchResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::SimulatePostLaunch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(simulation.markets.len(), 2);
+    assert!(
+        simulation.markets[0].average_price > simulation.markets[1].average_price,
+        "{:?}",
+        simulation.markets
+    );
+    for market in simulation.markets.iter() {
+        assert_eq!(market.lp_token_amount, Uint128::from(50_000_000_000u128));
+    }
+    assert!(simulation.markets[0].lp_stable_amount > simulation.markets[1].lp_stable_amount);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PostLaunch {
+            idx: Uint128::zero(),
+        },
+        &simulation.required_deposit,
+    )
+    .unwrap();
+}
+
+#[test]
+fn allowlist_bids() {
+    use crate::allowlist::leaf;
+    use crate::msg::{Allowlist, AllowlistBid, AllowlistEligibilityResponse};
+    use sha2::{Digest, Sha256};
+
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    schedule_sale(&mut app, &keiko_addr, sale_categories(None));
+
+    // two leaves, the bidder with a cap and another address without one
+    let cap = Uint128::from(700_000_000_000u128);
+    let bidder_leaf = leaf(&Addr::unchecked("bidder"), Some(cap));
+    let other_leaf = leaf(&Addr::unchecked("other"), None);
+    let mut pair = [bidder_leaf, other_leaf];
+    pair.sort_unstable();
+    let root: [u8; 32] = Sha256::digest(pair.concat()).into();
+    let proof = vec![hex::encode(other_leaf)];
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::SetAllowlist {
+                idx: Uint128::zero(),
+                allowlist: Some(Allowlist {
+                    merkle_root: "root".to_string(),
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::InvalidMerkleHash("root".to_string()));
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::SetAllowlist {
+            idx: Uint128::zero(),
+            allowlist: Some(Allowlist {
+                merkle_root: hex::encode(root),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotStart {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the allowlist cannot change while the round is live
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::SetAllowlist {
+            idx: Uint128::zero(),
+            allowlist: None,
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    // the cap is part of the leaf
+    let err = app
+        .execute_contract(
+            Addr::unchecked("bidder"),
+            keiko_addr.clone(),
+            &ExecuteMsg::AllowlistBid {
+                idx: Uint128::zero(),
+                premium_slot: 9,
+                cap: None,
+                proof: proof.clone(),
+            },
+            &[coin(600_000_000_000, "bid")],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::NotAllowlisted("bidder".to_string()));
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("bidder"),
+            keiko_addr.clone(),
+            &ExecuteMsg::AllowlistBid {
+                idx: Uint128::zero(),
+                premium_slot: 9,
+                cap: Some(cap),
+                proof: proof.clone(),
+            },
+            &[coin(800_000_000_000, "bid")],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::AllowlistCapExceeded(cap.to_string(), "800000000000".to_string())
+    );
+
+    app.execute_contract(
+        Addr::unchecked("bidder"),
+        keiko_addr.clone(),
+        &ExecuteMsg::AllowlistBid {
+            idx: Uint128::zero(),
+            premium_slot: 9,
+            cap: Some(cap),
+            proof: proof.clone(),
+        },
+        &[coin(600_000_000_000, "bid")],
+    )
+    .unwrap();
+
+    let bids: Vec<AllowlistBid> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::AllowlistBids {
+                idx: Uint128::zero(),
+                bidder: Addr::unchecked("bidder"),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(bids.len(), 1);
+    assert_eq!(bids[0].sale, 0);
+    assert_eq!(bids[0].amount, coin(600_000_000_000, "bid"));
+
+    let eligibility: AllowlistEligibilityResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::AllowlistEligibility {
+                idx: Uint128::zero(),
+                address: Addr::unchecked("bidder"),
+                denom: Denom::from("bid"),
+                cap: Some(cap),
+                proof: proof.clone(),
+            },
+        )
+        .unwrap();
+
+    assert!(eligibility.eligible);
+    assert_eq!(eligibility.bid, Uint128::from(600_000_000_000u128));
+    assert_eq!(
+        eligibility.remaining,
+        Some(Uint128::from(100_000_000_000u128))
+    );
+
+    // the cap of another bid denom is untouched
+    let eligibility: AllowlistEligibilityResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::AllowlistEligibility {
+                idx: Uint128::zero(),
+                address: Addr::unchecked("bidder"),
+                denom: Denom::from("bid2"),
+                cap: Some(cap),
+                proof: proof.clone(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(eligibility.bid, Uint128::zero());
+    assert_eq!(eligibility.remaining, Some(cap));
+
+    let eligibility: AllowlistEligibilityResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::AllowlistEligibility {
+                idx: Uint128::zero(),
+                address: Addr::unchecked("stranger"),
+                denom: Denom::from("bid"),
+                cap: None,
+                proof,
+            },
+        )
+        .unwrap();
+
+    assert!(!eligibility.eligible);
+
+    // the bid sits on the Orca queue of the sale
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    let pilot_sale: kujira_pilot::SaleResponse = app
+        .wrap()
+        .query_wasm_smart(
+            pilot_addr,
+            &kujira_pilot::QueryMsg::Sale {
+                idx: launch.pilots[0].idx.unwrap(),
+            },
+        )
+        .unwrap();
+    let balance = app
+        .wrap()
+        .query_balance(pilot_sale.orca_address.clone(), "bid")
+        .unwrap();
+
+    assert_eq!(balance.amount, Uint128::from(600_000_000_000u128));
+
+    // the bidder retracts the bid through Keiko while the sale is live
+    let before = app.wrap().query_balance("bidder", "bid").unwrap();
+    app.execute_contract(
+        Addr::unchecked("bidder"),
+        keiko_addr.clone(),
+        &ExecuteMsg::RetractAllowlistBid {
+            idx: Uint128::zero(),
+            sale: bids[0].sale,
+            bid_idx: bids[0].bid_idx,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let after = app.wrap().query_balance("bidder", "bid").unwrap();
+    assert_eq!(
+        after.amount - before.amount,
+        Uint128::from(600_000_000_000u128)
+    );
+    let balance = app
+        .wrap()
+        .query_balance(pilot_sale.orca_address, "bid")
+        .unwrap();
+    assert!(balance.amount.is_zero());
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("bidder"),
+            keiko_addr.clone(),
+            &ExecuteMsg::RetractAllowlistBid {
+                idx: Uint128::zero(),
+                sale: bids[0].sale,
+                bid_idx: bids[0].bid_idx,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::NothingToClaim("bidder".to_string()));
+}
+
+#[test]
+fn keeper_start() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    schedule_sale(&mut app, &keiko_addr, sale_categories(None));
+
+    let ready = |app: &CustomApp| -> Vec<Launch> {
+        app.wrap()
+            .query_wasm_smart(
+                keiko_addr.clone(),
+                &QueryMsg::LaunchesReadyToStart {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+
+    // the sale has not opened yet
+    assert!(ready(&app).is_empty());
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            keiko_addr.clone(),
+            &ExecuteMsg::KeeperStart {
+                idx: Uint128::zero(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::NotReadyToStart("0".to_string()));
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    assert_eq!(ready(&app).len(), 1);
+
+    // the launches are paged by idx
+    let page: Vec<Launch> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::LaunchesReadyToStart {
+                start_after: Some(Uint128::zero()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert!(page.is_empty());
+
+    // a single sale round of a managed token needs no funds, any sent are rejected
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotStart {
+                idx: Uint128::zero(),
+                round: None,
+            },
+            &coins(1_000_000, "usk"),
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidFunds {});
+
+    // the keeper reward is paid out of the funds the owner prefunded
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Prefund {
+            idx: Uint128::zero(),
+        },
+        &coins(1_500_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        keiko_addr.clone(),
+        &ExecuteMsg::KeeperStart {
+            idx: Uint128::zero(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::SaleLive);
+    assert!(launch.pilots[0].idx.is_some());
+    assert_eq!(launch.keeper_rewards, Uint128::from(1_000_000u128));
+    assert_eq!(launch.prefunded, coins(500_000, "usk"));
+    assert_eq!(
+        app.wrap().query_balance("keeper", "usk").unwrap().amount,
+        Uint128::from(1_000_000u128)
+    );
+    assert!(ready(&app).is_empty());
+
+    let history: Vec<LaunchHistoryEntry> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::LaunchHistory {
+                idx: Uint128::zero(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    let entry = history.last().unwrap();
+    assert_eq!(entry.action, LaunchAction::PilotStart);
+    assert_eq!(entry.sender, Addr::unchecked("keeper"));
+
+    // the prefund leaves the status unchanged but is recorded
+    let entry = &history[history.len() - 2];
+    assert_eq!(entry.action, LaunchAction::Prefund);
+    assert_eq!(entry.from, entry.to);
+}
+
+#[test]
+fn crank_post_launch() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    let idx = execute_sale(&mut app, &keiko_addr, &pilot_addr, sale_categories(None));
+
+    let awaiting = |app: &CustomApp| -> Vec<Launch> {
+        app.wrap()
+            .query_wasm_smart(
+                keiko_addr.clone(),
+                &QueryMsg::LaunchesAwaitingPostLaunch {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+
+    assert_eq!(awaiting(&app).len(), 1);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Prefund { idx },
+            &coins(5_000_000, "usk"),
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Prefund { idx },
+        &coins(5_000_000, "usk"),
+    )
+    .unwrap();
+
+    // the prefunded funds do not cover the BOW creation fee
+    let err = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            keiko_addr.clone(),
+            &ExecuteMsg::CrankPostLaunch { idx },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::InsufficientPrefund("10000000usk".to_string())
+    );
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Prefund { idx },
+        &coins(15_000_000, "usk"),
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Launch { idx })
+        .unwrap();
+    assert_eq!(launch.prefunded, coins(20_000_000, "usk"));
+
+    let balance = app.wrap().query_balance("launcher", "usk").unwrap().amount;
+
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        keiko_addr.clone(),
+        &ExecuteMsg::CrankPostLaunch { idx },
+        &[],
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Launch { idx })
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::LiquidityDeployed);
+    assert!(launch.prefunded.is_empty());
+    assert!(launch.bow.unwrap().contract_address.is_some());
+    assert!(awaiting(&app).is_empty());
+
+    // the prefund left after the BOW creation fee and the Pilot deposit are returned to the owner
+    assert!(
+        app.wrap().query_balance("launcher", "usk").unwrap().amount
+            >= balance + Uint128::from(10_000_000u128)
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            keiko_addr.clone(),
+            &ExecuteMsg::CrankPostLaunch { idx },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::NotAwaitingPostLaunch(idx.to_string()));
+}
+
+#[test]
+fn settle() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    schedule_sale(&mut app, &keiko_addr, sale_categories(None));
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotStart {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    let pilot_sale: kujira_pilot::SaleResponse = app
+        .wrap()
+        .query_wasm_smart(
+            pilot_addr.clone(),
+            &kujira_pilot::QueryMsg::Sale {
+                idx: launch.pilots[0].clone().idx.unwrap(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("bidder"),
+        pilot_sale.orca_address,
+        &kujira_orca::ExecuteMsg::SubmitBid {
+            premium_slot: 9,
+            delegate: None,
+            proof: None,
+        },
+        &[coin(600_000_000_000, "bid")],
+    )
+    .unwrap();
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 901);
+    app.set_block(new_block);
+
+    // the BOW creation fee of PostLaunch is paid with Settle
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Settle {
+                idx: Uint128::zero(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::InvalidFunds {});
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("bidder"),
+            keiko_addr.clone(),
+            &ExecuteMsg::Settle {
+                idx: Uint128::zero(),
+            },
+            &coins(10_000_000, "bid"),
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Settle {
+            idx: Uint128::zero(),
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(launch.status, LaunchStatus::LiquidityDeployed);
+    assert!(launch.pilots[0].result.is_some());
+    assert!(launch.liquidity.is_some());
+
+    let markets = launch.markets.unwrap();
+    assert_eq!(markets.len(), 1);
+    assert_eq!(markets[0].bid_denom, Denom::from("bid"));
+    assert_eq!(
+        launch.fin.unwrap().contract_address,
+        Some(markets[0].fin.clone())
+    );
+
+    let history: Vec<LaunchHistoryEntry> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::LaunchHistory {
+                idx: Uint128::zero(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    let actions: Vec<LaunchAction> = history
+        .iter()
+        .rev()
+        .take(2)
+        .map(|e| e.action.clone())
+        .collect();
+    assert_eq!(
+        actions,
+        vec![LaunchAction::PostLaunch, LaunchAction::PilotExecute]
+    );
+}
+
+#[test]
+fn pilot_schedule_validation() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories: sale_categories(None),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let now = app.block_info().time.seconds();
+    let sale = CreateSale {
+        title: "SNED".to_string(),
+        description: "SNED Launch".to_string(),
+        url: "https://example.com/sned".to_string(),
+        beneficiary: Addr::unchecked("beneficiary"),
+        price: Decimal::from_str("1").unwrap(),
+        opens: Timestamp::from_seconds(now + 100),
+        closes: Timestamp::from_seconds(now + 1000),
+    };
+    let orca = CreateOrca {
+        bid_denom: Denom::from("bid".to_string()),
+        max_slot: 9,
+        premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+        bid_threshold: Uint128::from(1_000_000_000u128),
+        waiting_period: 600,
+    };
+
+    let schedule = |app: &mut CustomApp, sale: CreateSale, orca: CreateOrca| {
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotSchedule {
+                idx: Uint128::zero(),
+                round: None,
+                recipient: None,
+                sale,
+                orca,
+                existing_fin: None,
+                market: None,
+            },
+            &[],
+        )
+        .map_err(|e| e.downcast::<ContractError>().unwrap())
+    };
+
+    let mut invalid = sale.clone();
+    invalid.price = Decimal::zero();
+    assert_eq!(
+        schedule(&mut app, invalid, orca.clone()).unwrap_err(),
+        ContractError::InvalidSalePrice {}
+    );
+
+    let mut invalid = sale.clone();
+    invalid.opens = Timestamp::from_seconds(now);
+    assert_eq!(
+        schedule(&mut app, invalid, orca.clone()).unwrap_err(),
+        ContractError::SaleOpensInPast(now.to_string())
+    );
+
+    let mut invalid = sale.clone();
+    invalid.closes = Timestamp::from_seconds(now + 100);
+    assert_eq!(
+        schedule(&mut app, invalid, orca.clone()).unwrap_err(),
+        ContractError::SaleClosesBeforeOpens((now + 100).to_string(), (now + 100).to_string())
+    );
+
+    let mut invalid = orca.clone();
+    invalid.waiting_period = 900;
+    assert_eq!(
+        schedule(&mut app, sale.clone(), invalid).unwrap_err(),
+        ContractError::WaitingPeriodTooLong("900".to_string())
+    );
+
+    let mut invalid = orca.clone();
+    invalid.max_slot = 0;
+    assert_eq!(
+        schedule(&mut app, sale.clone(), invalid).unwrap_err(),
+        ContractError::InvalidMaxSlot {}
+    );
+
+    let mut invalid = orca.clone();
+    invalid.premium_rate_per_slot = Decimal::from_str("0.2").unwrap();
+    assert_eq!(
+        schedule(&mut app, sale.clone(), invalid).unwrap_err(),
+        ContractError::InvalidPremiumRate("0.2".to_string())
+    );
+
+    let mut config: Config = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    config.pilot.sale_bounds = SaleBounds {
+        min_opens_delay: 200,
+        max_opens_delay: Some(86400),
+        min_duration: 100,
+        max_duration: Some(800),
+        min_price: Decimal::from_str("0.5").unwrap(),
+        max_price: None,
+        max_slot: Some(9),
+        max_premium: Some(Decimal::from_str("0.9").unwrap()),
+    };
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        keiko_addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            token: None,
+            tokenomics: None,
+            pilot: Some(config.pilot),
+            flows: None,
+            fin: Box::new(None),
+            bow: None,
+            launch: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let mut invalid = sale.clone();
+    invalid.price = Decimal::from_str("0.25").unwrap();
+    assert_eq!(
+        schedule(&mut app, invalid, orca.clone()).unwrap_err(),
+        ContractError::SaleParamOutOfBounds("price".to_string(), "0.25".to_string())
+    );
+
+    assert_eq!(
+        schedule(&mut app, sale.clone(), orca.clone()).unwrap_err(),
+        ContractError::SaleParamOutOfBounds("opens".to_string(), (now + 100).to_string())
+    );
+
+    let mut sale = sale;
+    sale.opens = Timestamp::from_seconds(now + 300);
+    sale.closes = Timestamp::from_seconds(now + 1200);
+    assert_eq!(
+        schedule(&mut app, sale.clone(), orca.clone()).unwrap_err(),
+        ContractError::SaleParamOutOfBounds("closes".to_string(), (now + 1200).to_string())
+    );
+
+    sale.closes = Timestamp::from_seconds(now + 1000);
+    let mut invalid = orca.clone();
+    invalid.max_slot = 10;
+    invalid.premium_rate_per_slot = Decimal::from_str("0.05").unwrap();
+    assert_eq!(
+        schedule(&mut app, sale.clone(), invalid).unwrap_err(),
+        ContractError::SaleParamOutOfBounds("max_slot".to_string(), "10".to_string())
+    );
+
+    let mut invalid = orca.clone();
+    invalid.premium_rate_per_slot = Decimal::from_str("0.105").unwrap();
+    assert_eq!(
+        schedule(&mut app, sale.clone(), invalid).unwrap_err(),
+        ContractError::SaleParamOutOfBounds(
+            "premium_rate_per_slot".to_string(),
+            "0.105".to_string()
+        )
+    );
+
+    schedule(&mut app, sale, orca).unwrap();
+}
+
+#[test]
+fn raise_estimates() {
+    let amount = Uint128::from(1_000_000_000_000u128);
+    let price = Decimal::from_str("0.25").unwrap();
+    let sale_fee = Decimal::from_str("0.05").unwrap();
+    let orca = |max_slot: u8, rate: &str| CreateOrca {
+        bid_denom: Denom::from("bid".to_string()),
+        max_slot,
+        premium_rate_per_slot: Decimal::from_str(rate).unwrap(),
+        bid_threshold: Uint128::zero(),
+        waiting_period: 0,
+    };
+
+    for rate in ["0.01", "0.025", "0.05", "0.1"] {
+        let mut last_min = None;
+        for max_slot in 1..=(1.0 / rate.parse::<f64>().unwrap()) as u8 - 1 {
+            let orca = orca(max_slot, rate);
+            let estimate = raise::estimate(amount, price, 6, 6, &orca, sale_fee).unwrap();
+
+            // a sold out sale raises between every token at the highest premium and the sale price
+            assert_eq!(estimate.max_raise, amount * price);
+            assert!(estimate.min_raise <= estimate.max_raise);
+            assert!(estimate.net_min_raise <= estimate.min_raise);
+            for slot in 0..=max_slot {
+                let raise = raise::slot_raise(amount, price, &orca, slot).unwrap();
+                assert!(raise >= estimate.min_raise && raise <= estimate.max_raise);
+            }
+
+            // more slots only lower the minimum raise
+            if let Some(last_min) = last_min {
+                assert!(estimate.min_raise < last_min);
+            }
+            last_min = Some(estimate.min_raise);
+
+            // the decimals the token has over the bid denom scale the raise down like PilotStart
+            for decimals in 6..=18u8 {
+                let scaled = amount * Uint128::from(10u128.pow((decimals - 6) as u32));
+                assert_eq!(
+                    raise::estimate(scaled, price, decimals, 6, &orca, sale_fee).unwrap(),
+                    estimate
+                );
+            }
+        }
+    }
+
+    assert_eq!(
+        raise::estimate(amount, price, 6, 6, &orca(10, "0.1"), sale_fee).unwrap_err(),
+        ContractError::InvalidPremiumRate("0.1".to_string())
+    );
+
+    let estimate: RaiseEstimate = raise::estimate(
+        amount,
+        Decimal::from_str("0.1").unwrap(),
+        6,
+        6,
+        &orca(10, "0.05"),
+        sale_fee,
+    )
+    .unwrap();
+    assert_eq!(
+        estimate,
+        RaiseEstimate {
+            min_raise: Uint128::from(50_000_000_000u128),
+            max_raise: Uint128::from(100_000_000_000u128),
+            net_min_raise: Uint128::from(47_500_000_000u128),
+        }
+    );
+}
+
+#[test]
+fn estimate_raise_query() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories: sale_categories(None),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let estimate: RaiseEstimate = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::EstimateRaise {
+                idx: Uint128::zero(),
+                round: None,
+                recipient: None,
+                price: Decimal::from_str("1").unwrap(),
+                orca: CreateOrca {
+                    bid_denom: Denom::from("bid".to_string()),
+                    max_slot: 9,
+                    premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+                    bid_threshold: Uint128::from(1_000_000_000u128),
+                    waiting_period: 600,
+                },
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        estimate,
+        RaiseEstimate {
+            min_raise: Uint128::from(100_000_000_000u128),
+            max_raise: Uint128::from(1_000_000_000_000u128),
+            net_min_raise: Uint128::from(95_000_000_000u128),
+        }
+    );
+
+    // the launch has a single round
+    let result: StdResult<RaiseEstimate> = app.wrap().query_wasm_smart(
+        keiko_addr.clone(),
+        &QueryMsg::EstimateRaise {
+            idx: Uint128::zero(),
+            round: Some(1),
+            recipient: None,
+            price: Decimal::from_str("1").unwrap(),
+            orca: CreateOrca {
+                bid_denom: Denom::from("bid".to_string()),
+                max_slot: 9,
+                premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+                bid_threshold: Uint128::from(1_000_000_000u128),
+                waiting_period: 600,
+            },
+        },
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn decimal_aware_pricing() {
+    let price = Decimal::from_str("0.5").unwrap();
+    for (token_decimals, bid_decimals, delta) in [(6u8, 6u8, 0i8), (18, 6, 12), (6, 18, -12)] {
+        assert_eq!(pricing::decimal_delta(token_decimals, bid_decimals), delta);
+
+        // 1000 tokens sold at 0.5 raise 500 of the bid denom
+        let sold = Uint128::from(1_000u128 * 10u128.pow(token_decimals as u32));
+        let raised = sold * pricing::base_price(price, delta);
+        assert_eq!(
+            raised,
+            Uint128::from(500u128 * 10u128.pow(bid_decimals as u32))
+        );
+        assert_eq!(
+            pricing::average_price(raised, sold, delta),
+            Decimal256::from_str("0.5").unwrap()
+        );
+        assert_eq!(
+            pricing::quote_amount(sold.multiply_ratio(1u128, 10u128), raised, sold),
+            raised.multiply_ratio(1u128, 10u128)
+        );
+
+        let estimate = raise::estimate(
+            sold,
+            price,
+            token_decimals,
+            bid_decimals,
+            &CreateOrca {
+                bid_denom: Denom::from("bid".to_string()),
+                max_slot: 10,
+                premium_rate_per_slot: Decimal::from_str("0.05").unwrap(),
+                bid_threshold: Uint128::zero(),
+                waiting_period: 0,
+            },
+            Decimal::zero(),
+        )
+        .unwrap();
+        assert_eq!(estimate.max_raise, raised);
+        assert_eq!(estimate.min_raise, raised.multiply_ratio(1u128, 2u128));
+    }
+}
+
+#[test]
+fn premium_slots() {
+    let rate = Decimal::from_str("0.03").unwrap();
+    for (premium, slot) in [
+        ("0", 0u8),
+        ("0.03", 1),
+        ("0.089999", 3),
+        ("0.27", 9),
+        ("0.2849", 9),
+    ] {
+        assert_eq!(
+            pricing::premium_slot(Decimal::from_str(premium).unwrap(), rate),
+            Some(slot),
+            "{premium}"
+        );
+    }
+    assert_eq!(pricing::premium_slot(rate, Decimal::zero()), None);
+}
+
+#[test]
+fn price_precision_policy() {
+    let policy = PricePrecisionPolicy::default();
+    for (price, decimals) in [
+        ("0.000000001234", 13),
+        ("0.0001234", 8),
+        ("0.1", 5),
+        ("1", 4),
+        ("9.99999", 4),
+        ("10", 3),
+        ("123.45", 3),
+        ("100000", 3),
+    ] {
+        assert_eq!(
+            pricing::price_precision(&policy, Decimal256::from_str(price).unwrap()),
+            decimals,
+            "{price}"
+        );
+    }
+
+    // the bounds of the policy cap the decimal places of very cheap and very expensive tokens
+    let policy = PricePrecisionPolicy {
+        significant_digits: 3,
+        min_decimal_places: 0,
+        max_decimal_places: 10,
+    };
+    for (price, decimals) in [
+        ("0.000000000000001", 10),
+        ("0.00123", 5),
+        ("1.5", 2),
+        ("1234567", 0),
+        ("0", 10),
+    ] {
+        assert_eq!(
+            pricing::price_precision(&policy, Decimal256::from_str(price).unwrap()),
+            decimals,
+            "{price}"
+        );
+    }
+}
+
+#[test]
+fn price_precision_override() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    let idx = execute_sale(&mut app, &keiko_addr, &pilot_addr, sale_categories(None));
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PricePrecision {
+                idx,
+                decimal_places: Some(2),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::InvalidPricePrecision("2".to_string()));
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("bidder"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PricePrecision {
+                idx,
+                decimal_places: Some(6),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // a policy is rejected with the value that is out of its bounds
+    let mut config: Config = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    config.fin.price_precision = PricePrecisionPolicy {
+        significant_digits: 5,
+        min_decimal_places: 12,
+        max_decimal_places: 10,
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked("owner"),
+            keiko_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                owner: None,
+                token: None,
+                tokenomics: None,
+                pilot: None,
+                flows: None,
+                fin: Box::new(Some(config.fin)),
+                bow: None,
+                launch: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::InvalidPricePrecisionPolicy(
+            "min_decimal_places".to_string(),
+            "12".to_string()
+        )
+    );
+
+    let simulation: SimulatePostLaunchResponse = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::SimulatePostLaunch { idx })
+        .unwrap();
+
+    assert_eq!(
+        simulation.price_precision_decimals,
+        pricing::price_precision(&PricePrecisionPolicy::default(), simulation.average_price)
+    );
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PricePrecision {
+            idx,
+            decimal_places: Some(10),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let simulation: SimulatePostLaunchResponse = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::SimulatePostLaunch { idx })
+        .unwrap();
+
+    assert_eq!(simulation.price_precision_decimals, 10);
+}
+
+#[test]
+fn price_precision_policy_instantiate() {
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let code_id = app
+        .wrap()
+        .query_wasm_contract_info(keiko_addr)
+        .unwrap()
+        .code_id;
+
+    let mut fin = config.fin;
+    fin.price_precision = PricePrecisionPolicy {
+        significant_digits: 5,
+        min_decimal_places: 12,
+        max_decimal_places: 10,
+    };
+    let err = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: config.owner,
+                token: config.token,
+                tokenomics: config.tokenomics,
+                pilot: config.pilot,
+                flows: config.flows,
+                fin,
+                bow: config.bow,
+                launch: config.launch,
+            },
+            &[],
+            "Keiko",
+            None,
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        ContractError::InvalidPricePrecisionPolicy(
+            "min_decimal_places".to_string(),
+            "12".to_string()
+        )
+    );
+}
+
+#[test]
+fn market_params() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    let mut config: Config = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    config.fin.fee_bounds.max_fee_maker = Some(Decimal256::from_str("0.001").unwrap());
+    config.fin.fee_bounds.max_fee_taker = Some(Decimal256::from_str("0.003").unwrap());
+    config.bow.bounds = BowBounds {
+        min_interval: Decimal::from_str("0.0001").unwrap(),
+        max_interval: Some(Decimal::from_str("0.1").unwrap()),
+        max_intervals: Some(5),
+        min_fee: Decimal::from_str("0.0001").unwrap(),
+        max_fee: Some(Decimal::from_str("0.01").unwrap()),
+        min_amp: Decimal::one(),
+        max_amp: Some(Decimal::from_str("50").unwrap()),
+    };
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        keiko_addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            token: None,
+            tokenomics: None,
+            pilot: None,
+            flows: None,
+            fin: Box::new(Some(config.fin)),
+            bow: Some(config.bow.clone()),
+            launch: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Create {
+            terms_conditions_accepted: true,
+        },
+        &coins(1_000_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Token {
+            idx: Uint128::zero(),
+            create: true,
+            symbol: Some("SNED".to_string()),
+            denom: None,
+            decimals: None,
+            denom_admin: None,
+            png_url: None,
+            svg_url: None,
+        },
+        &coins(10_000_000, "usk"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::Tokenomics {
+            idx: Uint128::zero(),
+            categories: sale_categories(None),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let schedule = |app: &mut CustomApp, market: MarketParams| {
+        let opens = app.block_info().time.seconds() + 100;
+        app.execute_contract(
+            Addr::unchecked("launcher"),
+            keiko_addr.clone(),
+            &ExecuteMsg::PilotSchedule {
+                idx: Uint128::zero(),
+                round: None,
+                recipient: None,
+                sale: CreateSale {
+                    title: "SNED".to_string(),
+                    description: "SNED Launch".to_string(),
+                    url: "https://example.com/sned".to_string(),
+                    beneficiary: Addr::unchecked("beneficiary"),
+                    price: Decimal::from_str("1").unwrap(),
+                    opens: Timestamp::from_seconds(opens),
+                    closes: Timestamp::from_seconds(opens + 900),
+                },
+                orca: CreateOrca {
+                    bid_denom: Denom::from("bid".to_string()),
+                    max_slot: 9,
+                    premium_rate_per_slot: Decimal::from_str("0.1").unwrap(),
+                    bid_threshold: Uint128::from(1_000_000_000u128),
+                    waiting_period: 600,
+                },
+                existing_fin: None,
+                market: Some(market),
+            },
+            &[],
+        )
+        .map_err(|e| e.downcast::<ContractError>().unwrap())
+    };
+
+    for (market, name, value) in [
+        (
+            MarketParams {
+                fee_maker: Some(Decimal256::from_str("0.002").unwrap()),
+                ..MarketParams::default()
+            },
+            "fee_maker",
+            "0.002",
+        ),
+        (
+            MarketParams {
+                bow_intervals: Some(vec![Decimal::from_str("0.2").unwrap()]),
+                ..MarketParams::default()
+            },
+            "bow_intervals",
+            "0.2",
+        ),
+        (
+            MarketParams {
+                bow_intervals: Some(vec![Decimal::from_str("0.01").unwrap(); 6]),
+                ..MarketParams::default()
+            },
+            "bow_intervals",
+            "6",
+        ),
+        (
+            MarketParams {
+                bow_amp: Some(Decimal::from_str("100").unwrap()),
+                ..MarketParams::default()
+            },
+            "bow_amp",
+            "100",
+        ),
+    ] {
+        assert_eq!(
+            schedule(&mut app, market).unwrap_err(),
+            ContractError::MarketParamOutOfBounds(name.to_string(), value.to_string())
+        );
+    }
+
+    let market = MarketParams {
+        fee_maker: Some(Decimal256::from_str("0.0005").unwrap()),
+        fee_taker: None,
+        bow_intervals: Some(vec![
+            Decimal::from_str("0.002").unwrap(),
+            Decimal::from_str("0.01").unwrap(),
+        ]),
+        bow_fee: None,
+        bow_amp: Some(Decimal::from_str("20").unwrap()),
+        liquidity_pricing: None,
+        leftover: None,
+    };
+    schedule(&mut app, market.clone()).unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    assert_eq!(launch.market_params, Some(market.clone()));
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotStart {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    let pilot_sale: kujira_pilot::SaleResponse = app
+        .wrap()
+        .query_wasm_smart(
+            pilot_addr.clone(),
+            &kujira_pilot::QueryMsg::Sale {
+                idx: launch.pilots[0].clone().idx.unwrap(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("bidder"),
+        pilot_sale.orca_address,
+        &kujira_orca::ExecuteMsg::SubmitBid {
+            premium_slot: 9,
+            delegate: None,
+            proof: None,
+        },
+        &[coin(600_000_000_000, "bid")],
+    )
+    .unwrap();
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 901);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotExecute {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let simulation: SimulatePostLaunchResponse = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::SimulatePostLaunch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    let instantiated: Vec<Binary> = simulation
+        .messages
+        .iter()
+        .filter_map(|m| match m {
+            CosmosMsg::Wasm(WasmMsg::Instantiate2 { msg, .. }) => Some(msg.clone()),
+            _ => None,
+        })
+        .collect();
+
+    // the FIN pair and the BOW market maker use the parameters of the launch
+    let fin: kujira_fin::InstantiateMsg = from_json(&instantiated[0]).unwrap();
+    assert_eq!(fin.fee_maker, market.fee_maker.unwrap());
+    assert_eq!(fin.fee_taker, Decimal256::from_str("0.0015").unwrap());
+
+    let bow: kujira::bow::market_maker::InstantiateMsg = from_json(&instantiated[1]).unwrap();
+    assert_eq!(bow.intervals, market.bow_intervals.unwrap());
+    assert_eq!(bow.amp, market.bow_amp.unwrap());
+    assert_eq!(bow.fee, config.bow.fee);
+}
+
+#[test]
+fn liquidity_pricing() {
+    let simulate = |market: Option<MarketParams>| -> (Launch, SimulatePostLaunchResponse) {
+        let (mut app, keiko_addr, pilot_addr) = setup();
+        let idx = execute_sale_with_market(
+            &mut app,
+            &keiko_addr,
+            &pilot_addr,
+            sale_categories(None),
+            market,
+        );
+        let launch: Launch = app
+            .wrap()
+            .query_wasm_smart(keiko_addr.clone(), &QueryMsg::Launch { idx })
+            .unwrap();
+        let simulation: SimulatePostLaunchResponse = app
+            .wrap()
+            .query_wasm_smart(keiko_addr.clone(), &QueryMsg::SimulatePostLaunch { idx })
+            .unwrap();
+        (launch, simulation)
+    };
+    let sent = |simulation: &SimulatePostLaunchResponse, to: &str| -> Vec<Coin> {
+        simulation
+            .messages
+            .iter()
+            .filter_map(|m| match m {
+                CosmosMsg::Bank(BankMsg::Send { to_address, amount }) if to_address == to => {
+                    Some(amount.clone())
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    };
+    let liquidity = Uint128::from(100_000_000_000u128);
+
+    // the average price of the raise seeds the whole liquidity allocation
+    let (launch, average) = simulate(None);
+    let result = launch.pilots[0].result.clone().unwrap();
+    let average_stable = result
+        .raise_total
+        .multiply_ratio(liquidity, Uint128::from(1_000_000_000_000u128));
+    let denom = launch.token.unwrap().denom.to_string();
+    assert_eq!(average.markets[0].lp_stable_amount, average_stable);
+    assert_eq!(average.markets[0].lp_token_amount, liquidity);
+    assert_eq!(
+        average.beneficiary_funds.amount,
+        result.raise_amount - average_stable
+    );
+
+    // the bids cleared at the highest premium slot, which is never above the average price
+    let (_, clearing) = simulate(Some(MarketParams {
+        liquidity_pricing: Some(LiquidityPricing::ClearingPrice),
+        ..MarketParams::default()
+    }));
+    assert!(clearing.markets[0].lp_stable_amount <= average_stable);
+    assert_eq!(clearing.markets[0].lp_token_amount, liquidity);
+    assert_eq!(
+        clearing.beneficiary_funds.amount,
+        result.raise_amount - clearing.markets[0].lp_stable_amount
+    );
+
+    // a floor above the average price seeds fewer tokens with the bid denom of the average price,
+    // the beneficiary receives the leftover tokens
+    let floor = Decimal::from_str("0.5").unwrap();
+    let (_, floored) = simulate(Some(MarketParams {
+        liquidity_pricing: Some(LiquidityPricing::Floor { price: floor }),
+        ..MarketParams::default()
+    }));
+    let floor_tokens = average_stable.multiply_ratio(2u128, 1u128);
+    assert_eq!(floored.markets[0].lp_stable_amount, average_stable);
+    assert_eq!(floored.markets[0].lp_token_amount, floor_tokens);
+    assert!(
+        sent(&floored, "beneficiary").contains(&coin((liquidity - floor_tokens).u128(), &denom))
+    );
+
+    // a floor below the average price keeps the average price
+    let (_, unfloored) = simulate(Some(MarketParams {
+        liquidity_pricing: Some(LiquidityPricing::Floor {
+            price: Decimal::from_str("0.0001").unwrap(),
+        }),
+        ..MarketParams::default()
+    }));
+    assert_eq!(unfloored.markets[0].lp_stable_amount, average_stable);
+    assert_eq!(unfloored.markets[0].lp_token_amount, liquidity);
+
+    // the treasury receives the leftover tokens and the beneficiary pays the average price
+    let (_, treasury) = simulate(Some(MarketParams {
+        liquidity_pricing: Some(LiquidityPricing::Floor { price: floor }),
+        leftover: Some(LiquidityLeftover::Treasury {
+            address: Addr::unchecked("treasury"),
+        }),
+        ..MarketParams::default()
+    }));
+    assert_eq!(
+        sent(&treasury, "treasury"),
+        vec![coin((liquidity - floor_tokens).u128(), &denom)]
+    );
+    assert!(!sent(&treasury, "beneficiary")
+        .iter()
+        .any(|c| c.denom == denom));
+    assert_eq!(
+        treasury.beneficiary_funds.amount,
+        result.raise_amount - average_stable
+    );
+}
+
+#[test]
+fn sale_analytics() {
+    let (mut app, keiko_addr, pilot_addr) = setup();
+    let idx = execute_sale(&mut app, &keiko_addr, &pilot_addr, sale_categories(None));
+
+    let analytics: Vec<SaleAnalytics> = app
+        .wrap()
+        .query_wasm_smart(keiko_addr.clone(), &QueryMsg::SaleAnalytics { idx })
+        .unwrap();
+    assert_eq!(analytics.len(), 1);
+    let sale = &analytics[0];
+    assert_eq!(sale.sale, 0);
+    assert_eq!(sale.round, 0);
+    assert_eq!(sale.amount, Uint128::from(1_000_000_000_000u128));
+
+    // the only bids sit in slot 9 at a 90% premium, they buy every token at 0.1
+    assert_eq!(sale.total_bid_amount, Uint128::from(600_000_000_000u128));
+    assert_eq!(sale.filled_amount, Uint128::from(100_000_000_000u128));
+    assert_eq!(sale.unfilled_amount, Uint128::from(500_000_000_000u128));
+    assert_eq!(
+        sale.clearing_premium,
+        Some(Decimal::from_str("0.9").unwrap())
+    );
+    assert_eq!(sale.clearing_price, Some(Decimal::from_str("0.1").unwrap()));
+
+    let slot = sale.slots.iter().find(|s| s.slot == 9).unwrap();
+    assert_eq!(slot.premium_rate, Decimal::from_str("0.9").unwrap());
+    assert_eq!(slot.total_bid_amount, sale.total_bid_amount);
+    assert_eq!(slot.filled_amount, sale.filled_amount);
+    assert_eq!(slot.unfilled_amount, sale.unfilled_amount);
+    assert!(sale
+        .slots
+        .iter()
+        .filter(|s| s.slot != 9)
+        .all(|s| s.total_bid_amount.is_zero()));
+}
+
+#[test]
+fn claim_for_bidder() {
+    use crate::allowlist::leaf;
+    use crate::msg::{Allowlist, BidderPosition};
+
+    let (mut app, keiko_addr, pilot_addr) = setup();
+
+    schedule_sale(&mut app, &keiko_addr, sale_categories(None));
+
+    // the bidder is the only leaf, the root is its leaf
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::SetAllowlist {
+            idx: Uint128::zero(),
+            allowlist: Some(Allowlist {
+                merkle_root: hex::encode(leaf(&Addr::unchecked("bidder"), None)),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotStart {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // one bid through Keiko and one placed by the bidder directly on Orca
+    app.execute_contract(
+        Addr::unchecked("bidder"),
+        keiko_addr.clone(),
+        &ExecuteMsg::AllowlistBid {
+            idx: Uint128::zero(),
+            premium_slot: 9,
+            cap: None,
+            proof: vec![],
+        },
+        &[coin(300_000_000_000, "bid")],
+    )
+    .unwrap();
+
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    let pilot_sale: kujira_pilot::SaleResponse = app
+        .wrap()
+        .query_wasm_smart(
+            pilot_addr,
+            &kujira_pilot::QueryMsg::Sale {
+                idx: launch.pilots[0].idx.unwrap(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("bidder"),
+        pilot_sale.orca_address.clone(),
+        &kujira_orca::ExecuteMsg::SubmitBid {
+            premium_slot: 9,
+            delegate: None,
+            proof: None,
+        },
+        &[coin(300_000_000_000, "bid")],
+    )
+    .unwrap();
+
+    let positions = |app: &CustomApp| -> Vec<BidderPosition> {
+        app.wrap()
+            .query_wasm_smart(
+                keiko_addr.clone(),
+                &QueryMsg::BidderPositions {
+                    bidder: Addr::unchecked("bidder"),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+
+    // the queue is recorded once the sale is created, the bids of the live sale are listed but
+    // nothing is claimed before the sale is executed
+    let launch: Launch = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::Launch {
+                idx: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        launch.pilots[0].orca_address,
+        Some(pilot_sale.orca_address.clone())
+    );
+    let live = positions(&app);
+    assert_eq!(live.len(), 2);
+    assert!(live.iter().all(|p| p.live));
+    let err = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            keiko_addr.clone(),
+            &ExecuteMsg::ClaimForBidder {
+                idx: Uint128::zero(),
+                bidder: Addr::unchecked("bidder"),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim("bidder".to_string()));
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 901);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotExecute {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let denom = launch.token.unwrap().denom.to_string();
+
+    let before = positions(&app);
+    assert_eq!(before.len(), 2);
+    assert!(before.iter().all(|p| !p.live));
+    assert!(before
+        .iter()
+        .all(|p| p.orca_address == pilot_sale.orca_address));
+    let keiko: Vec<&BidderPosition> = before.iter().filter(|p| p.keiko).collect();
+    assert_eq!(keiko.len(), 1);
+    assert_eq!(keiko[0].premium_slot, 9);
+
+    // the bid of the executed sale is claimed with its liquidated collateral, not retracted
+    let err = app
+        .execute_contract(
+            Addr::unchecked("bidder"),
+            keiko_addr.clone(),
+            &ExecuteMsg::RetractAllowlistBid {
+                idx: Uint128::zero(),
+                sale: keiko[0].sale,
+                bid_idx: keiko[0].bid_idx,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidStatus {});
+
+    let bid_balance = app.wrap().query_balance("bidder", "bid").unwrap().amount;
+    let token_balance = app.wrap().query_balance("bidder", &denom).unwrap().amount;
+
+    // anyone claims for the bidder, the funds go to the bidder
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        keiko_addr.clone(),
+        &ExecuteMsg::ClaimForBidder {
+            idx: Uint128::zero(),
+            bidder: Addr::unchecked("bidder"),
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap().query_balance("bidder", "bid").unwrap().amount,
+        bid_balance + keiko[0].retractable.amount
+    );
+    assert_eq!(
+        app.wrap().query_balance("bidder", &denom).unwrap().amount,
+        token_balance + keiko[0].claimable.amount
+    );
+
+    // the direct bid is left for the bidder to claim on Orca
+    let after = positions(&app);
+    assert_eq!(after.len(), 1);
+    assert!(!after[0].keiko);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            keiko_addr.clone(),
+            &ExecuteMsg::ClaimForBidder {
+                idx: Uint128::zero(),
+                bidder: Addr::unchecked("bidder"),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim("bidder".to_string()));
+}
+
+#[test]
+fn claim_for_bidder_retracted() {
+    use crate::allowlist::leaf;
+    use crate::msg::{Allowlist, BidderPosition};
+
+    let (mut app, keiko_addr, _pilot_addr) = setup();
+
+    schedule_sale(&mut app, &keiko_addr, sale_categories(None));
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::SetAllowlist {
+            idx: Uint128::zero(),
+            allowlist: Some(Allowlist {
+                merkle_root: hex::encode(leaf(&Addr::unchecked("bidder"), None)),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let mut new_block = app.block_info();
+    new_block.time = Timestamp::from_seconds(new_block.time.seconds() + 100);
+    app.set_block(new_block);
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotStart {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("bidder"),
+        keiko_addr.clone(),
+        &ExecuteMsg::AllowlistBid {
+            idx: Uint128::zero(),
+            premium_slot: 9,
+            cap: None,
+            proof: vec![],
+        },
+        &[coin(300_000_000_000, "bid")],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("launcher"),
+        keiko_addr.clone(),
+        &ExecuteMsg::PilotRetract {
+            idx: Uint128::zero(),
+            round: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let positions: Vec<BidderPosition> = app
+        .wrap()
+        .query_wasm_smart(
+            keiko_addr.clone(),
+            &QueryMsg::BidderPositions {
+                bidder: Addr::unchecked("bidder"),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(positions.len(), 1);
+    assert!(!positions[0].live);
+    assert!(positions[0].claimable.amount.is_zero());
+
+    // the bid of the retracted sale is retracted and refunded to the bidder
+    let bid_balance = app.wrap().query_balance("bidder", "bid").unwrap().amount;
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        keiko_addr.clone(),
+        &ExecuteMsg::ClaimForBidder {
+            idx: Uint128::zero(),
+            bidder: Addr::unchecked("bidder"),
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap().query_balance("bidder", "bid").unwrap().amount,
+        bid_balance + positions[0].retractable.amount
+    );
+    assert_eq!(
+        positions[0].retractable.amount,
+        Uint128::from(300_000_000_000u128)
+    );
+}
+
+#[test]
+fn migrate_legacy_launches() {
+    use crate::migrations::{legacy_launch, migrate_launches, LegacyLaunch, LegacyLaunchStatus};
+    use crate::msg::Fin;
+    use crate::state::{LAUNCH_HISTORY, MIGRATION_CURSOR};
+    use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::OwnedDeps;
+    use std::marker::PhantomData;
+
+    let (app, keiko_addr, _pilot_addr) = setup();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(keiko_addr, &QueryMsg::Config {})
+        .unwrap();
+
+    // the wasm queries of the migration fail against the mock querier
+    let mut deps: OwnedDeps<MockStorage, MockApi, MockQuerier<KujiraQuery>, KujiraQuery> =
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: MockQuerier::new(&[]),
+            custom_query_type: PhantomData,
+        };
+
+    let env = mock_env();
+    let owner = Addr::unchecked("owner");
+    let legacy = |idx: u128, status: LegacyLaunchStatus, fin: Option<Fin>| LegacyLaunch {
+        idx: Uint128::from(idx),
+        owner: Addr::unchecked("launcher"),
+        deposit: coin(1_000_000_000, "usk"),
+        terms_conditions_accepted: Some(true),
+        status,
+        token: None,
+        tokenomics: None,
+        pilot: None,
+        fin,
+        bow: None,
+    };
+    for launch in [
+        legacy(0, LegacyLaunchStatus::Created, None),
+        legacy(1, LegacyLaunchStatus::Planned, None),
+        legacy(
+            2,
+            LegacyLaunchStatus::Completed,
+            Some(Fin {
+                contract_address: Some(Addr::unchecked("fin")),
+            }),
+        ),
+    ] {
+        legacy_launch()
+            .save(deps.as_mut().storage, launch.idx.u128(), &launch)
+            .unwrap();
+    }
+
+    // the first batch leaves the cursor on the next launch
+    let (migrated, pending) =
+        migrate_launches(deps.as_mut(), &env, &owner, &config, Some(2)).unwrap();
+    assert_eq!((migrated, pending), (2, true));
+    assert_eq!(MIGRATION_CURSOR.load(deps.as_ref().storage).unwrap(), 2);
+    assert_eq!(
+        Launch::load(deps.as_ref().storage, Uint128::zero())
+            .unwrap()
+            .status,
+        LaunchStatus::Created
+    );
+    assert_eq!(
+        Launch::load(deps.as_ref().storage, Uint128::one())
+            .unwrap()
+            .status,
+        LaunchStatus::Scheduled
+    );
+    assert!(Launch::load(deps.as_ref().storage, Uint128::from(2u128)).is_err());
+    let entry = LAUNCH_HISTORY.load(deps.as_ref().storage, (1, 0)).unwrap();
+    assert_eq!(entry.action, LaunchAction::Migrate);
+    assert_eq!(entry.sender, owner);
+    assert_eq!(entry.to, LaunchStatus::Scheduled);
+
+    // a launch with its FIN pair has deployed its liquidity, the FIN pair that cannot be queried
+    // is not taken as launched
+    let (migrated, pending) =
+        migrate_launches(deps.as_mut(), &env, &owner, &config, Some(2)).unwrap();
+    assert_eq!((migrated, pending), (1, false));
+    assert!(MIGRATION_CURSOR
+        .may_load(deps.as_ref().storage)
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        Launch::load(deps.as_ref().storage, Uint128::from(2u128))
+            .unwrap()
+            .status,
+        LaunchStatus::LiquidityDeployed
+    );
+
+    // a later upgrade finds no legacy launch and leaves no migration pending
+    let (migrated, pending) =
+        migrate_launches(deps.as_mut(), &env, &owner, &config, Some(2)).unwrap();
+    assert_eq!((migrated, pending), (0, false));
+    assert!(MIGRATION_CURSOR
+        .may_load(deps.as_ref().storage)
+        .unwrap()
+        .is_none());
+
+    // the migrated launches are in the owner and status index
+    assert_eq!(
+        Launch::active_count_by_owner(deps.as_ref().storage, &Addr::unchecked("launcher")).unwrap(),
+        3
+    );
+}